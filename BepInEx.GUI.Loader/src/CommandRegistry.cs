@@ -0,0 +1,156 @@
+﻿namespace BepInEx.GUI.Loader;
+
+/// <summary>
+/// Commands that can be typed in the console of the GUI.
+/// Handlers are called from the game main thread, once the chainloader is done.
+/// </summary>
+public static class CommandRegistry
+{
+    /// <returns>The text shown in the GUI console.</returns>
+    public delegate string CommandHandler(string[] args);
+
+    private class Command
+    {
+        internal string Name;
+        internal string Description;
+        internal CommandHandler Handler;
+    }
+
+    private static readonly object _commandsLock = new();
+    private static readonly Dictionary<string, Command> _commands = new(StringComparer.OrdinalIgnoreCase);
+
+    // Received from the GUI, waiting for the main thread
+    private static readonly object _pendingCommandLinesLock = new();
+    private static readonly Queue<string> _pendingCommandLines = new();
+    private static volatile bool _isChainloaderDone;
+
+    static CommandRegistry()
+    {
+        Register("help", "Lists the available commands", _ => ToCommandListText());
+    }
+
+    /// <summary>
+    /// Adds a command, replacing the one with the same name. The GUI gets the new list for autocompletion.
+    /// </summary>
+    public static void Register(string name, string description, CommandHandler handler)
+    {
+        if (string.IsNullOrWhiteSpace(name) || name.Contains(" "))
+        {
+            throw new ArgumentException($"Invalid command name: \"{name}\"", nameof(name));
+        }
+
+        lock (_commandsLock)
+        {
+            _commands[name] = new Command { Name = name, Description = description ?? "", Handler = handler };
+        }
+
+        SendLogToClientSocket.Instance?.SendCommandList();
+    }
+
+    public static bool Unregister(string name)
+    {
+        bool removed;
+        lock (_commandsLock)
+        {
+            removed = _commands.Remove(name);
+        }
+
+        if (removed)
+        {
+            SendLogToClientSocket.Instance?.SendCommandList();
+        }
+
+        return removed;
+    }
+
+    /// <summary>
+    /// Called from the socket thread, the response gets sent once the main thread ran the command.
+    /// </summary>
+    internal static void Enqueue(string commandLine)
+    {
+        lock (_pendingCommandLinesLock)
+        {
+            _pendingCommandLines.Enqueue(commandLine);
+        }
+
+        RunPendingCommands();
+    }
+
+    internal static void OnChainloaderDone()
+    {
+        _isChainloaderDone = true;
+
+        RunPendingCommands();
+    }
+
+    private static void RunPendingCommands()
+    {
+        if (!_isChainloaderDone)
+        {
+            return;
+        }
+
+        lock (_pendingCommandLinesLock)
+        {
+            while (_pendingCommandLines.Count > 0)
+            {
+                var commandLine = _pendingCommandLines.Dequeue();
+                if (!MainThread.TryInvoke(() => SendResponse(commandLine, Execute(commandLine))))
+                {
+                    SendResponse(commandLine, "Can't run commands, the game main thread couldn't be reached");
+                }
+            }
+        }
+    }
+
+    private static void SendResponse(string commandLine, string response)
+    {
+        SendLogToClientSocket.Instance?.SendCommandResponse(commandLine, response);
+    }
+
+    private static string Execute(string commandLine)
+    {
+        var words = commandLine.Split((char[])null, StringSplitOptions.RemoveEmptyEntries);
+        if (words.Length == 0)
+        {
+            return "";
+        }
+
+        Command command;
+        lock (_commandsLock)
+        {
+            _commands.TryGetValue(words[0], out command);
+        }
+
+        if (command == null)
+        {
+            return $"Unknown command \"{words[0]}\", type help for the list of commands";
+        }
+
+        try
+        {
+            var args = new string[words.Length - 1];
+            Array.Copy(words, 1, args, 0, args.Length);
+
+            return command.Handler(args) ?? "";
+        }
+        catch (Exception e)
+        {
+            return $"{command.Name} failed: {e}";
+        }
+    }
+
+    internal static string ToCommandListText()
+    {
+        lock (_commandsLock)
+        {
+            var text = new StringBuilder();
+            foreach (var command in _commands.Values)
+            {
+                text.Append(command.Name).Append('\t').Append(command.Description).Append('\n');
+            }
+
+            return text.ToString();
+        }
+    }
+}
@@ -3,33 +3,60 @@ using BepInEx.Logging;
 
 namespace BepInEx.GUI.Loader;
 
+// Values of the log level field that aren't a log, BepInEx levels being positive flags
+internal static class PacketKind
+{
+    // GUI -> loader, the text is the command line
+    internal const Int32 Command = -1;
+    // loader -> GUI, output of a command
+    internal const Int32 CommandResponse = -2;
+    // loader -> GUI, one `name\tdescription` per line
+    internal const Int32 CommandList = -3;
+    // loader -> GUI, once the chainloader is done, one `guid\tname\tversion\tdll path\tdependencies` per plugin
+    internal const Int32 PluginInfoList = -4;
+    // loader -> GUI, first packet of each connection, the text is LogPacket.ProtocolVersion
+    internal const Int32 ProtocolVersion = -5;
+}
+
 internal unsafe struct LogPacket
 {
     internal byte[] Bytes;
 
+    // Bumped whenever the layout changes, the GUI can't read the packets of another version
+    internal const string ProtocolVersion = "2";
+
     // Field                        - Offset
     // Log String Byte Array Length - 0x0000
     // Log Level                    - 0x0004
-    // Log String Byte Array        - 0x0008
+    // Timestamp                    - 0x0008
+    // Log String Byte Array        - 0x0010
+
+    internal const Int32 SizeOfLengthPrefix = sizeof(UInt32);
+    internal const Int32 SizeOfLogLevel = sizeof(Int32);
+    // Unix time in milliseconds of the packet being made, so that the GUI times the logs as they got logged
+    internal const Int32 SizeOfTimestamp = sizeof(Int64);
 
-    internal unsafe LogPacket(LogEventArgs log)
+    internal LogPacket(LogEventArgs log) : this((Int32)log.Level, log.ToString())
     {
-        var logStringByteArray = Encoding.UTF8.GetBytes(log.ToString());
+    }
 
-        var payloadSize = logStringByteArray.Length;
+    internal unsafe LogPacket(Int32 kind, string text)
+    {
+        var logStringByteArray = Encoding.UTF8.GetBytes(text);
 
-        const Int32 SizeOfLengthPrefix = sizeof(UInt32);
-        const Int32 SizeOfLogLevel = sizeof(Int32);
+        var payloadSize = logStringByteArray.Length;
 
-        Bytes = new byte[SizeOfLengthPrefix + SizeOfLogLevel + payloadSize];
+        Bytes = new byte[SizeOfLengthPrefix + SizeOfLogLevel + SizeOfTimestamp + payloadSize];
 
         fixed (byte* byteArrayPtr = Bytes)
         {
             *(UInt32*)byteArrayPtr = (UInt32)payloadSize;
 
-            *(Int32*)(&byteArrayPtr[SizeOfLengthPrefix]) = (Int32)log.Level;
+            *(Int32*)(&byteArrayPtr[SizeOfLengthPrefix]) = kind;
+
+            *(Int64*)(&byteArrayPtr[SizeOfLengthPrefix + SizeOfLogLevel]) = DateTimeOffset.UtcNow.ToUnixTimeMilliseconds();
 
-            Marshal.Copy(logStringByteArray, 0, (IntPtr)(&byteArrayPtr[SizeOfLengthPrefix + SizeOfLogLevel]), payloadSize);
+            Marshal.Copy(logStringByteArray, 0, (IntPtr)(&byteArrayPtr[SizeOfLengthPrefix + SizeOfLogLevel + SizeOfTimestamp]), payloadSize);
         }
     }
 }
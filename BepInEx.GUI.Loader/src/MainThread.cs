@@ -0,0 +1,51 @@
+﻿using System.Reflection;
+
+namespace BepInEx.GUI.Loader;
+
+/// <summary>
+/// Runs actions on the game main thread, from the Update of the hidden ThreadingHelper MonoBehaviour of BepInEx.
+/// Found through reflection since its assembly depends on the BepInEx version and the Unity runtime.
+/// </summary>
+internal static class MainThread
+{
+    private static readonly string[] ThreadingHelperTypeNames =
+    {
+        "BepInEx.ThreadingHelper",
+        "BepInEx.Unity.Mono.ThreadingHelper",
+        "BepInEx.Unity.IL2CPP.ThreadingHelper",
+    };
+
+    private static Action<Action> _startSyncInvoke;
+
+    /// <returns>false when there is no ThreadingHelper, the chainloader makes it when starting.</returns>
+    internal static bool TryInvoke(Action action)
+    {
+        _startSyncInvoke ??= FindStartSyncInvoke();
+        if (_startSyncInvoke == null)
+        {
+            return false;
+        }
+
+        _startSyncInvoke(action);
+        return true;
+    }
+
+    private static Action<Action> FindStartSyncInvoke()
+    {
+        foreach (var assembly in AppDomain.CurrentDomain.GetAssemblies())
+        {
+            foreach (var typeName in ThreadingHelperTypeNames)
+            {
+                var type = assembly.GetType(typeName, false);
+                var instance = type?.GetProperty("Instance", BindingFlags.Static | BindingFlags.Public)?.GetValue(null, null);
+                var startSyncInvoke = type?.GetMethod("StartSyncInvoke", new[] { typeof(Action) });
+                if (instance != null && startSyncInvoke != null)
+                {
+                    return action => startSyncInvoke.Invoke(instance, new object[] { action });
+                }
+            }
+        }
+
+        return null;
+    }
+}
@@ -0,0 +1,36 @@
+﻿#if BEPINEX_5
+using System.Linq;
+using BepInEx.Bootstrap;
+#endif
+
+namespace BepInEx.GUI.Loader;
+
+// What the logs of the chainloader don't tell about the loaded plugins
+internal static class PluginInfoList
+{
+    // One `guid\tname\tversion\tdll path\tdependencies` per plugin,
+    // dependencies being comma separated GUIDs, soft ones ending with `?`
+    internal static string ToText()
+    {
+        var text = new StringBuilder();
+
+#if BEPINEX_5
+        foreach (var pluginInfo in Chainloader.PluginInfos.Values)
+        {
+            var dependencies = pluginInfo.Dependencies.Select(dependency =>
+                (dependency.Flags & BepInDependency.DependencyFlags.SoftDependency) != 0 ?
+                    dependency.DependencyGUID + "?" :
+                    dependency.DependencyGUID);
+
+            text.Append(pluginInfo.Metadata.GUID).Append('\t')
+                .Append(pluginInfo.Metadata.Name).Append('\t')
+                .Append(pluginInfo.Metadata.Version).Append('\t')
+                .Append(pluginInfo.Location).Append('\t')
+                .Append(string.Join(",", dependencies.ToArray()))
+                .Append('\n');
+        }
+#endif
+
+        return text.ToString();
+    }
+}
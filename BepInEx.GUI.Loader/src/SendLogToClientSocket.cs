@@ -1,4 +1,5 @@
-﻿using System.Net;
+﻿using System.IO;
+using System.Net;
 using System.Net.Sockets;
 using System.Threading;
 using BepInEx.Logging;
@@ -12,7 +13,7 @@ internal class SendLogToClientSocket : ILogListener
     private readonly Thread _thread;
 
     private readonly object _queueLock = new();
-    private readonly Queue<LogEventArgs> _logQueue = new();
+    private readonly Queue<LogPacket> _packetQueue = new();
 
     private bool _isDisposed = false;
 
@@ -43,6 +44,20 @@ internal class SendLogToClientSocket : ILogListener
                     break;
                 }
 
+                // before anything else, so that the GUI can tell whether it understands the packets
+                try
+                {
+                    clientSocket.Send(new LogPacket(PacketKind.ProtocolVersion, LogPacket.ProtocolVersion).Bytes);
+                }
+                catch (Exception e)
+                {
+                    Log.LogError($"Error while trying to send the protocol version to socket: {e}");
+                    continue;
+                }
+
+                StartReceivingCommands(clientSocket);
+                SendCommandList();
+
                 SendPacketsToClientUntilConnectionIsClosed(clientSocket);
             }
         });
@@ -59,18 +74,17 @@ internal class SendLogToClientSocket : ILogListener
                 break;
             }
 
-            while (_logQueue.Count > 0)
+            while (_packetQueue.Count > 0)
             {
-                LogEventArgs log;
+                LogPacket packet;
                 lock (_queueLock)
                 {
-                    log = _logQueue.Peek();
+                    packet = _packetQueue.Peek();
                 }
-                var logPacket = new LogPacket(log);
 
                 try
                 {
-                    clientSocket.Send(logPacket.Bytes);
+                    clientSocket.Send(packet.Bytes);
                 }
                 catch (Exception e)
                 {
@@ -80,25 +94,72 @@ internal class SendLogToClientSocket : ILogListener
 
                 lock (_queueLock)
                 {
-                    _ = _logQueue.Dequeue();
+                    _ = _packetQueue.Dequeue();
                 }
             }
         }
     }
 
-    public void Dispose()
+    // Commands typed in the GUI console, ran on the main thread then answered through the regular packet queue
+    private void StartReceivingCommands(Socket clientSocket)
     {
+        new Thread(() =>
+        {
+            try
+            {
+                using var stream = new NetworkStream(clientSocket, false);
+                using var reader = new BinaryReader(stream, Encoding.UTF8);
 
+                while (!_isDisposed)
+                {
+                    var payloadSize = reader.ReadUInt32();
+                    var kind = reader.ReadInt32();
+                    var text = Encoding.UTF8.GetString(reader.ReadBytes((int)payloadSize));
+
+                    if (kind == PacketKind.Command)
+                    {
+                        CommandRegistry.Enqueue(text);
+                    }
+                }
+            }
+            catch (Exception e) when (e is IOException || e is ObjectDisposedException)
+            {
+                // the GUI closed the connection, the send loop takes care of it
+            }
+        })
+        {
+            IsBackground = true,
+        }.Start();
     }
 
-    internal void StoreLog(LogEventArgs eventArgs)
+    internal void SendCommandList()
+    {
+        EnqueuePacket(new LogPacket(PacketKind.CommandList, CommandRegistry.ToCommandListText()));
+    }
+
+    internal void SendCommandResponse(string commandLine, string response)
+    {
+        EnqueuePacket(new LogPacket(PacketKind.CommandResponse, $"> {commandLine}{Environment.NewLine}{response}".TrimEnd()));
+    }
+
+    private void EnqueuePacket(LogPacket packet)
     {
         lock (_queueLock)
         {
-            _logQueue.Enqueue(eventArgs);
+            _packetQueue.Enqueue(packet);
         }
     }
 
+    public void Dispose()
+    {
+
+    }
+
+    internal void StoreLog(LogEventArgs eventArgs)
+    {
+        EnqueuePacket(new LogPacket(eventArgs));
+    }
+
     public void LogEvent(object sender, LogEventArgs eventArgs)
     {
         if (_isDisposed)
@@ -107,5 +168,11 @@ internal class SendLogToClientSocket : ILogListener
         }
 
         StoreLog(eventArgs);
+
+        if (eventArgs.Data?.ToString() == "Chainloader startup complete" && eventArgs.Level.Equals(LogLevel.Message))
+        {
+            EnqueuePacket(new LogPacket(PacketKind.PluginInfoList, PluginInfoList.ToText()));
+            CommandRegistry.OnChainloaderDone();
+        }
     }
 }
@@ -3,14 +3,20 @@ use sysinfo::Pid;
 use eframe::CreationContext;
 use eframe::{self, *};
 
-use std::sync::{atomic::AtomicBool, Arc};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
 
 use crossbeam_channel::Receiver;
 
-use views::tabs::{console::ConsoleTab, general::GeneralTab, settings::SettingsTab, Tab};
+use views::tabs::{
+    configs::ConfigsTab, console::ConsoleTab, dependencies::DependenciesTab, general::GeneralTab,
+    history::HistoryTab, profiles::ProfilesTab, settings::SettingsTab, stats::StatsTab, Tab,
+};
 
-use crate::backend::{process, window};
+use crate::backend::network::commands::CommandChannel;
+use crate::backend::{alerts::Alerts, process, session_recorder::SessionRecorder, window};
 use crate::config::launch::AppLaunchConfig;
 use crate::config::Config;
 use crate::data::bepinex_log::receiver::LogReceiver;
@@ -33,11 +39,26 @@ pub struct BepInExGUI {
 
     pub log_receiver_thread: Option<LogReceiver>,
 
+    pub alerts: Option<Alerts>,
+
+    pub session_recorder: Option<SessionRecorder>,
+
     pub should_update_window_title: Arc<AtomicBool>,
 
     pub dark_theme: egui::Style,
 }
 
+// What the log receiver shares with each tab
+struct TabReceivers {
+    general_tab_mod_r: Receiver<BepInExMod>,
+    console_tab_mod_r: Receiver<BepInExMod>,
+    dependencies_tab_mod_r: Receiver<BepInExMod>,
+    general_tab_log_r: Receiver<BepInExLogEntry>,
+    log_r: Receiver<BepInExLogEntry>,
+    stats_log_r: Receiver<BepInExLogEntry>,
+    command_channel: CommandChannel,
+}
+
 const FPS_15: Duration = Duration::from_micros(66666);
 
 impl App for BepInExGUI {
@@ -54,8 +75,17 @@ impl App for BepInExGUI {
 
     fn save(&mut self, storage: &mut dyn eframe::Storage) {
         eframe::set_value(storage, NAME, &self.config);
+        if let Some(session_recorder) = &self.session_recorder {
+            session_recorder.save(self.config.session_history_limit);
+        }
         _ = self.config.save_bepinex_toml_cfg_file();
     }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        if let Some(session_recorder) = &mut self.session_recorder {
+            session_recorder.finish(self.config.session_history_limit);
+        }
+    }
 }
 
 impl Default for BepInExGUI {
@@ -67,6 +97,8 @@ impl Default for BepInExGUI {
             should_exit_app: Arc::default(),
             tabs: Vec::default(),
             log_receiver_thread: Option::default(),
+            alerts: Option::default(),
+            session_recorder: Option::default(),
             should_update_window_title: Arc::default(),
             dark_theme: theme::get_dark_theme(),
         }
@@ -94,17 +126,18 @@ impl BepInExGUI {
             cc.egui_ctx.set_visuals(egui::Visuals::light());
         }
         self.config.update_text_styles(&cc.egui_ctx);
+        self.config.update_log_colors();
+        self.config.log_rules.rules_changed();
 
-        self.start_thread_exit_gui_if_target_process_not_alive(
-            self.app_launch_config.target_process_id(),
-        );
-
-        window::window_topmost_on_target_start::init(self.app_launch_config.target_process_id());
+        if !self.app_launch_config.is_offline() {
+            self.start_thread_exit_gui_if_target_process_not_alive(
+                self.app_launch_config.target_process_id(),
+            );
 
-        let (general_tab_mod_r, console_tab_mod_r, log_r) =
-            self.init_log_receiver(self.app_launch_config.log_socket_port_receiver());
+            window::window_topmost_on_target_start::init(self.app_launch_config.target_process_id());
+        }
 
-        self.init_tabs(general_tab_mod_r, console_tab_mod_r, log_r);
+        self.init_log_source_and_tabs();
 
         self.config.bepinex_gui_csharp_cfg_full_path = self
             .app_launch_config
@@ -116,41 +149,102 @@ impl BepInExGUI {
         self
     }
 
-    fn init_log_receiver(
-        &mut self,
-        log_socket_port_receiver: u16,
-    ) -> (
-        Receiver<BepInExMod>,
-        Receiver<BepInExMod>,
-        Receiver<BepInExLogEntry>,
-    ) {
+    /// Switches to viewing the given `LogOutput.log`, starting over with fresh tabs.
+    pub fn open_log_file(&mut self, log_file_full_path: PathBuf) {
+        tracing::info!("Opening log file {:?}", log_file_full_path);
+
+        self.app_launch_config = AppLaunchConfig::offline(Some(log_file_full_path));
+        self.init_log_source_and_tabs();
+
+        self.should_update_window_title.store(true, Ordering::Relaxed);
+    }
+
+    fn init_log_source_and_tabs(&mut self) {
+        let tab_receivers = self.init_log_receiver();
+
+        self.tabs.clear();
+        self.init_tabs(tab_receivers);
+    }
+
+    fn init_log_receiver(&mut self) -> TabReceivers {
         let (general_tab_mod_s, general_tab_mod_r) = crossbeam_channel::unbounded();
         let (console_tab_mod_s, console_tab_mod_r) = crossbeam_channel::unbounded();
+        let (dependencies_tab_mod_s, dependencies_tab_mod_r) = crossbeam_channel::unbounded();
+        let (general_tab_log_s, general_tab_log_r) = crossbeam_channel::unbounded();
         let (log_s, log_r) = crossbeam_channel::unbounded();
+        let (stats_log_s, stats_log_r) = crossbeam_channel::unbounded();
+        let mut log_senders = vec![general_tab_log_s, log_s, stats_log_s];
+        let mut mod_senders = vec![general_tab_mod_s, console_tab_mod_s, dependencies_tab_mod_s];
+
+        // alerts and history are about the running game, not about a log file being viewed
+        if self.app_launch_config.is_offline() {
+            self.alerts = None;
+            self.session_recorder = None;
+        } else {
+            let (alerts_log_s, alerts_log_r) = crossbeam_channel::unbounded();
+            log_senders.push(alerts_log_s);
+            self.alerts = Some(Alerts::new(alerts_log_r));
+
+            let (session_log_s, session_log_r) = crossbeam_channel::unbounded();
+            let (session_mod_s, session_mod_r) = crossbeam_channel::unbounded();
+            log_senders.push(session_log_s);
+            mod_senders.push(session_mod_s);
+            self.session_recorder = Some(SessionRecorder::new(
+                &self.app_launch_config,
+                session_log_r,
+                session_mod_r,
+            ));
+        }
 
+        let command_channel = CommandChannel::default();
         let log_receiver = LogReceiver::new(
-            log_socket_port_receiver,
-            vec![log_s],
-            vec![general_tab_mod_s, console_tab_mod_s],
+            self.app_launch_config.log_socket_port_receiver(),
+            self.app_launch_config.bepinex_log_output_file_full_path().clone(),
+            log_senders,
+            mod_senders,
+            command_channel.clone(),
         );
-        log_receiver.start_thread_loop();
+        if self.app_launch_config.is_offline() {
+            if let Some(log_file_full_path) = self.app_launch_config.offline_log_file() {
+                log_receiver.start_thread_read_file(log_file_full_path.clone());
+            }
+        } else {
+            log_receiver.start_thread_loop();
+        }
         self.log_receiver_thread = Some(log_receiver);
 
-        (general_tab_mod_r, console_tab_mod_r, log_r)
+        TabReceivers {
+            general_tab_mod_r,
+            console_tab_mod_r,
+            dependencies_tab_mod_r,
+            general_tab_log_r,
+            log_r,
+            stats_log_r,
+            command_channel,
+        }
     }
 
-    fn init_tabs(
-        &mut self,
-        general_tab_mod_r: Receiver<BepInExMod>,
-        console_tab_mod_r: Receiver<BepInExMod>,
-        log_r: Receiver<BepInExLogEntry>,
-    ) {
-        self.tabs.push(Box::new(GeneralTab::new(general_tab_mod_r)));
+    fn init_tabs(&mut self, tab_receivers: TabReceivers) {
+        self.tabs.push(Box::new(GeneralTab::new(
+            tab_receivers.general_tab_mod_r,
+            tab_receivers.general_tab_log_r,
+            self.session_recorder.as_ref().map(SessionRecorder::session),
+            &self.app_launch_config,
+        )));
         self.tabs.push(Box::new(ConsoleTab::new(
-            console_tab_mod_r,
-            log_r,
+            tab_receivers.console_tab_mod_r,
+            tab_receivers.log_r,
+            tab_receivers.command_channel,
             self.should_exit_app.clone(),
         )));
+        self.tabs.push(Box::new(StatsTab::new(tab_receivers.stats_log_r)));
+        self.tabs.push(Box::new(DependenciesTab::new(
+            tab_receivers.dependencies_tab_mod_r,
+            &self.app_launch_config,
+        )));
+        self.tabs.push(Box::new(HistoryTab::new()));
+        self.tabs.push(Box::new(ProfilesTab::new()));
+        self.tabs.push(Box::new(ConfigsTab::new()));
         self.tabs.push(Box::new(SettingsTab::new()));
     }
 
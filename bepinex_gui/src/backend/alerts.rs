@@ -0,0 +1,224 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use crossbeam_channel::Receiver;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter};
+
+use crate::data::bepinex_log::{BepInExLogEntry, LogLevel, CHAINLOADER_DONE_MESSAGE};
+
+pub const TOAST_DURATION: Duration = Duration::from_secs(6);
+const MAX_TOAST_COUNT: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumIter, Serialize, Deserialize)]
+pub enum AlertCondition {
+    #[strum(serialize = "Log level")]
+    LevelThreshold,
+    Regex,
+    #[strum(serialize = "Load complete")]
+    LoadComplete,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AlertTrigger {
+    pub enabled: bool,
+    pub condition: AlertCondition,
+    // `LevelThreshold`: this level or more severe
+    pub level: LogLevel,
+    // `Regex`: matched against the log text
+    pub pattern: String,
+    pub only_when_unfocused: bool,
+    pub cooldown_secs: f32,
+
+    pub toast: bool,
+    pub title_badge: bool,
+    pub taskbar_attention: bool,
+    pub sound: bool,
+}
+
+impl Default for AlertTrigger {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            condition: AlertCondition::LevelThreshold,
+            level: LogLevel::Error,
+            pattern: String::new(),
+            only_when_unfocused: true,
+            cooldown_secs: 10.,
+            toast: true,
+            title_badge: true,
+            taskbar_attention: true,
+            sound: false,
+        }
+    }
+}
+
+pub fn default_triggers() -> Vec<AlertTrigger> {
+    vec![
+        AlertTrigger::default(),
+        AlertTrigger {
+            condition: AlertCondition::LoadComplete,
+            title_badge: false,
+            ..Default::default()
+        },
+    ]
+}
+
+pub struct Toast {
+    pub text: String,
+    pub level: LogLevel,
+    pub created_at: Instant,
+}
+
+/// What the app should do with the window this frame.
+#[derive(Default)]
+pub struct AlertActions {
+    pub taskbar_attention: bool,
+    pub sound: bool,
+}
+
+pub struct Alerts {
+    log_receiver: Receiver<BepInExLogEntry>,
+    // per trigger index
+    last_fired: HashMap<usize, Instant>,
+    // per trigger index, with the pattern it was built from
+    regexes: HashMap<usize, (String, Option<Regex>)>,
+    toasts: Vec<Toast>,
+    badge_count: usize,
+}
+
+impl Alerts {
+    pub fn new(log_receiver: Receiver<BepInExLogEntry>) -> Self {
+        Self {
+            log_receiver,
+            last_fired: HashMap::new(),
+            regexes: HashMap::new(),
+            toasts: Vec::new(),
+            badge_count: 0,
+        }
+    }
+
+    pub fn toasts(&self) -> &[Toast] {
+        self.toasts.as_ref()
+    }
+
+    pub fn dismiss_toast(&mut self, toast_index: usize) {
+        self.toasts.remove(toast_index);
+    }
+
+    /// Alerts received since the window was last focused, shown in the window title.
+    pub const fn badge_count(&self) -> usize {
+        self.badge_count
+    }
+
+    pub fn update(&mut self, triggers: &[AlertTrigger], is_window_focused: bool) -> AlertActions {
+        let mut actions = AlertActions::default();
+
+        if is_window_focused {
+            self.badge_count = 0;
+        }
+
+        self.toasts
+            .retain(|toast| toast.created_at.elapsed() < TOAST_DURATION);
+
+        while let Ok(log) = self.log_receiver.try_recv() {
+            for (trigger_index, trigger) in triggers.iter().enumerate() {
+                if !trigger.enabled || (trigger.only_when_unfocused && is_window_focused) {
+                    continue;
+                }
+
+                if !self.is_match(trigger_index, trigger, &log) {
+                    continue;
+                }
+
+                let cooldown = Duration::from_secs_f32(trigger.cooldown_secs.max(0.));
+                let is_cooling_down = self
+                    .last_fired
+                    .get(&trigger_index)
+                    .is_some_and(|last_fired| last_fired.elapsed() < cooldown);
+                if is_cooling_down {
+                    continue;
+                }
+                self.last_fired.insert(trigger_index, Instant::now());
+
+                self.fire(trigger, &log, &mut actions);
+            }
+        }
+
+        actions
+    }
+
+    fn is_match(&mut self, trigger_index: usize, trigger: &AlertTrigger, log: &BepInExLogEntry) -> bool {
+        match trigger.condition {
+            AlertCondition::LevelThreshold => log.level() <= trigger.level,
+            AlertCondition::Regex => {
+                if trigger.pattern.is_empty() {
+                    return false;
+                }
+
+                let is_outdated = self
+                    .regexes
+                    .get(&trigger_index)
+                    .is_none_or(|(pattern, _)| *pattern != trigger.pattern);
+                if is_outdated {
+                    let regex = RegexBuilder::new(&trigger.pattern)
+                        .case_insensitive(true)
+                        .build()
+                        .ok();
+                    self.regexes
+                        .insert(trigger_index, (trigger.pattern.clone(), regex));
+                }
+
+                self.regexes[&trigger_index]
+                    .1
+                    .as_ref()
+                    .is_some_and(|regex| regex.is_match(log.data()))
+            }
+            AlertCondition::LoadComplete => log.message() == CHAINLOADER_DONE_MESSAGE,
+        }
+    }
+
+    fn fire(&mut self, trigger: &AlertTrigger, log: &BepInExLogEntry, actions: &mut AlertActions) {
+        if trigger.toast {
+            let text = if trigger.condition == AlertCondition::LoadComplete {
+                "The game finished loading".to_string()
+            } else {
+                log.message().lines().next().unwrap_or_default().to_string()
+            };
+
+            if self.toasts.len() == MAX_TOAST_COUNT {
+                self.toasts.remove(0);
+            }
+            self.toasts.push(Toast {
+                text,
+                level: log.level(),
+                created_at: Instant::now(),
+            });
+        }
+
+        if trigger.title_badge {
+            self.badge_count += 1;
+        }
+
+        actions.taskbar_attention |= trigger.taskbar_attention;
+        actions.sound |= trigger.sound;
+    }
+}
+
+#[cfg(windows)]
+pub fn play_sound() {
+    use winapi::um::winuser::{MessageBeep, MB_ICONEXCLAMATION};
+
+    unsafe {
+        MessageBeep(MB_ICONEXCLAMATION);
+    }
+}
+
+#[cfg(not(windows))]
+pub fn play_sound() {
+    // todo
+}
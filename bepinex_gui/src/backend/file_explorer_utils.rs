@@ -25,6 +25,8 @@ pub fn open_path_in_explorer(file: &PathBuf) {
     }
 }
 
+// the `return`s keep every platform branch alike
+#[allow(clippy::needless_return)]
 pub fn highlight_path_in_explorer(file: &Path) {
     if let Err(e) = (|| {
         #[cfg(target_os = "windows")]
@@ -38,10 +40,7 @@ pub fn highlight_path_in_explorer(file: &Path) {
                     .raw_arg("/select,\"".to_string() + s)
                     .spawn();
             } else {
-                Err(std::io::Error::new(
-                    std::io::ErrorKind::Other,
-                    "Can't convert PathBuf to_str",
-                ))
+                Err(std::io::Error::other("Can't convert PathBuf to_str"))
             }
         }
 
@@ -63,3 +62,18 @@ pub fn highlight_path_in_explorer(file: &Path) {
         tracing::error!("{}", e);
     }
 }
+
+/// Lets the user pick a `LogOutput.log` to view.
+pub fn pick_log_file() -> Option<PathBuf> {
+    match native_dialog::FileDialog::new()
+        .set_title("Open a BepInEx log file")
+        .add_filter("Log files", &["log", "txt"])
+        .show_open_single_file()
+    {
+        Ok(log_file_full_path) => log_file_full_path,
+        Err(err) => {
+            tracing::error!("Failed showing the file dialog: {}", err);
+            None
+        }
+    }
+}
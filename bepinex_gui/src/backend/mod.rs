@@ -1,22 +1,25 @@
 use std::sync::atomic::Ordering;
 
-use eframe::Frame;
+use eframe::{egui::UserAttentionType, Frame};
 
 use crate::app::BepInExGUI;
 
+pub mod alerts;
 pub mod file_explorer_utils;
 pub mod network;
 mod panic_handler;
 pub mod process;
 mod reset_app_if_window_hang;
+pub mod session_recorder;
 pub mod thunderstore;
 pub mod window;
 
 impl BepInExGUI {
     pub(crate) fn backend_update(&mut self, frame: &mut Frame) {
-        // surely at some point something will need this (clueless)
+        self.update_alerts(frame);
+
         if self.should_update_window_title.swap(false, Ordering::AcqRel) {
-            frame.set_window_title(self.app_launch_config.window_title());
+            frame.set_window_title(&self.window_title());
         }
 
         if self.should_exit_app.load(Ordering::Relaxed) {
@@ -25,6 +28,40 @@ impl BepInExGUI {
     }
 }
 
+impl BepInExGUI {
+    fn update_alerts(&mut self, frame: &mut Frame) {
+        if let Some(alerts) = &mut self.alerts {
+            let window_info = &frame.info().window_info;
+            let is_window_focused = window_info.focused && !window_info.minimized;
+
+            let previous_badge_count = alerts.badge_count();
+            let actions = alerts.update(&self.config.alert_triggers, is_window_focused);
+
+            if actions.taskbar_attention {
+                frame.request_user_attention(UserAttentionType::Critical);
+            }
+
+            if actions.sound {
+                alerts::play_sound();
+            }
+
+            if alerts.badge_count() != previous_badge_count {
+                self.should_update_window_title.store(true, Ordering::Relaxed);
+            }
+        }
+    }
+
+    // e.g. `(3) BepInExGUI 5.4.21 - Risk of Rain 2` when 3 alerts were missed
+    fn window_title(&self) -> String {
+        let title = self.app_launch_config.window_title();
+
+        match &self.alerts {
+            Some(alerts) if alerts.badge_count() > 0 => format!("({}) {title}", alerts.badge_count()),
+            _ => title.to_string(),
+        }
+    }
+}
+
 pub fn init() {
     panic_handler::init();
 
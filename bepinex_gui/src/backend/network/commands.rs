@@ -0,0 +1,84 @@
+use std::{
+    io,
+    net::TcpStream,
+    sync::{Arc, Mutex},
+};
+
+use super::packet_protocol;
+
+#[derive(Clone)]
+pub struct ConsoleCommand {
+    pub name: String,
+    pub description: String,
+}
+
+/// Sends the console commands to the loader, and keeps the ones it advertises.
+///
+/// Shared between the log receiver, which owns the connection, and the console.
+#[derive(Clone, Default)]
+pub struct CommandChannel {
+    // `None` while not connected
+    tcp_stream: Arc<Mutex<Option<TcpStream>>>,
+    available_commands: Arc<Mutex<Vec<ConsoleCommand>>>,
+}
+
+impl CommandChannel {
+    pub fn connect(&self, tcp_stream: &TcpStream) {
+        match tcp_stream.try_clone() {
+            Ok(tcp_stream) => *self.tcp_stream.lock().unwrap() = Some(tcp_stream),
+            Err(err) => tracing::error!("Failed sharing the socket for commands: {}", err),
+        }
+    }
+
+    pub fn disconnect(&self) {
+        *self.tcp_stream.lock().unwrap() = None;
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.tcp_stream.lock().unwrap().is_some()
+    }
+
+    pub fn send(&self, command_line: &str) -> io::Result<()> {
+        match self.tcp_stream.lock().unwrap().as_mut() {
+            Some(tcp_stream) => packet_protocol::write_packet(
+                tcp_stream,
+                packet_protocol::COMMAND_PACKET_KIND,
+                command_line,
+            ),
+            None => Err(io::Error::new(
+                io::ErrorKind::NotConnected,
+                "Not connected to the game",
+            )),
+        }
+    }
+
+    /// From the text of a command list packet, one `name\tdescription` per line.
+    pub fn set_available_commands(&self, command_list_text: &str) {
+        let commands = command_list_text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let (name, description) = line.split_once('\t').unwrap_or((line, ""));
+                ConsoleCommand {
+                    name: name.trim().to_string(),
+                    description: description.trim().to_string(),
+                }
+            })
+            .collect();
+
+        *self.available_commands.lock().unwrap() = commands;
+    }
+
+    /// Commands starting with `prefix`, case insensitive.
+    pub fn completions(&self, prefix: &str) -> Vec<ConsoleCommand> {
+        let prefix = prefix.to_lowercase();
+
+        self.available_commands
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|command| command.name.to_lowercase().starts_with(&prefix))
+            .cloned()
+            .collect()
+    }
+}
@@ -1,12 +1,38 @@
-use byteorder::{NativeEndian, ReadBytesExt};
+use byteorder::{NativeEndian, ReadBytesExt, WriteBytesExt};
 
-use std::io::{Cursor, Read};
+use std::io::{Cursor, Read, Write};
 
 use std::mem::size_of;
 use std::net::TcpStream;
+use std::time::{Duration, SystemTime};
 
 use crate::data::bepinex_log::LogLevel;
 
+// The log level field of a packet, BepInEx levels are positive flags,
+// the other kinds of packets use negative values
+pub const COMMAND_PACKET_KIND: i32 = -1;
+pub const COMMAND_RESPONSE_PACKET_KIND: i32 = -2;
+pub const COMMAND_LIST_PACKET_KIND: i32 = -3;
+pub const PLUGIN_INFO_LIST_PACKET_KIND: i32 = -4;
+// first packet of the loader, its text is the protocol version
+pub const PROTOCOL_VERSION_PACKET_KIND: i32 = -5;
+
+/// Version of the packet layout, the GUI and the loader have to be upgraded together.
+///
+/// Packets of the loader are: length, kind, timestamp, then the utf8 text.
+/// Version 1 had no timestamp and no version packet.
+pub const PROTOCOL_VERSION: u32 = 2;
+
+pub enum PacketKind {
+    Log(LogLevel),
+    // output of a command sent by the GUI
+    CommandResponse,
+    // commands the loader knows, one `name\tdescription` per line
+    CommandList,
+    // sent once the chainloader is done, one `guid\tname\tversion\tdll path\tdependencies` per plugin
+    PluginInfoList,
+}
+
 pub fn read_packet_length(tcp_stream: &mut TcpStream) -> Result<usize, std::io::Error> {
     const HEADER_SIZE: usize = size_of::<u32>();
 
@@ -18,16 +44,82 @@ pub fn read_packet_length(tcp_stream: &mut TcpStream) -> Result<usize, std::io::
     Ok(packet_length)
 }
 
-pub fn read_packet_log_level(tcp_stream: &mut TcpStream) -> Result<LogLevel, std::io::Error> {
-    unsafe {
-        let mut received_bytes = read_packet_internal(tcp_stream, size_of::<i32>())?;
-
-        let log_level: LogLevel = std::mem::transmute::<i32, LogLevel>(
-            Cursor::new(&mut received_bytes).read_i32::<NativeEndian>()?,
-        );
+/// Reads the version packet the loader starts with.
+///
+/// Fails with `InvalidData` when the loader doesn't speak the same protocol,
+/// none of its packets can be read then.
+pub fn read_handshake(tcp_stream: &mut TcpStream) -> Result<(), std::io::Error> {
+    let packet_length = read_packet_length(tcp_stream)?;
+
+    let mut received_bytes = read_packet_internal(tcp_stream, size_of::<i32>())?;
+    if Cursor::new(&mut received_bytes).read_i32::<NativeEndian>()? != PROTOCOL_VERSION_PACKET_KIND {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("the loader is older than protocol version {PROTOCOL_VERSION}"),
+        ));
+    }
 
-        Ok(log_level)
+    read_packet_timestamp(tcp_stream)?;
+    let version = packet_bytes_to_utf8_string(&read_packet(tcp_stream, packet_length)?);
+    if version.trim() != PROTOCOL_VERSION.to_string() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("the loader uses protocol version {version}, not {PROTOCOL_VERSION}"),
+        ));
     }
+
+    Ok(())
+}
+
+pub fn read_packet_kind(tcp_stream: &mut TcpStream) -> Result<PacketKind, std::io::Error> {
+    let mut received_bytes = read_packet_internal(tcp_stream, size_of::<i32>())?;
+
+    let packet_kind = match Cursor::new(&mut received_bytes).read_i32::<NativeEndian>()? {
+        COMMAND_RESPONSE_PACKET_KIND => PacketKind::CommandResponse,
+        COMMAND_LIST_PACKET_KIND => PacketKind::CommandList,
+        PLUGIN_INFO_LIST_PACKET_KIND => PacketKind::PluginInfoList,
+        log_level => PacketKind::Log(log_level_from_flags(log_level)),
+    };
+
+    Ok(packet_kind)
+}
+
+/// When the loader made the packet, sent after the kind.
+pub fn read_packet_timestamp(tcp_stream: &mut TcpStream) -> Result<SystemTime, std::io::Error> {
+    let mut received_bytes = read_packet_internal(tcp_stream, size_of::<i64>())?;
+
+    let unix_time_in_ms = Cursor::new(&mut received_bytes).read_i64::<NativeEndian>()?;
+
+    Ok(SystemTime::UNIX_EPOCH + Duration::from_millis(unix_time_in_ms.max(0) as u64))
+}
+
+// The most severe of the flags, custom levels being shown as messages
+fn log_level_from_flags(flags: i32) -> LogLevel {
+    [
+        LogLevel::Fatal,
+        LogLevel::Error,
+        LogLevel::Warning,
+        LogLevel::Message,
+        LogLevel::Info,
+        LogLevel::Debug,
+    ]
+    .into_iter()
+    .find(|level| flags & *level as i32 != 0)
+    .unwrap_or(LogLevel::Message)
+}
+
+/// Length, kind, then the utf8 text, the loader doesn't read a timestamp like the one it sends.
+pub fn write_packet(
+    tcp_stream: &mut TcpStream,
+    kind: i32,
+    text: &str,
+) -> Result<(), std::io::Error> {
+    let mut packet_bytes = Vec::with_capacity(size_of::<u32>() + size_of::<i32>() + text.len());
+    packet_bytes.write_u32::<NativeEndian>(text.len() as u32)?;
+    packet_bytes.write_i32::<NativeEndian>(kind)?;
+    packet_bytes.extend_from_slice(text.as_bytes());
+
+    tcp_stream.write_all(&packet_bytes)
 }
 
 pub fn read_packet(
@@ -66,5 +158,5 @@ fn read_packet_internal(
 }
 
 pub fn packet_bytes_to_utf8_string(packet_bytes: &[u8]) -> String {
-    unsafe { std::str::from_utf8_unchecked(packet_bytes).to_string() }
+    String::from_utf8_lossy(packet_bytes).into_owned()
 }
@@ -24,6 +24,8 @@ use winapi::{
     },
 };
 
+use crate::config::launch::AppLaunchConfig;
+
 #[cfg(windows)]
 pub fn for_each_thread(target_process_id: Pid, callback: impl Fn(HANDLE)) -> bool {
     use sysinfo::PidExt;
@@ -195,3 +197,22 @@ pub(crate) fn spawn_thread_check_if_process_is_hung(
     out_true_when_process_is_dead: Arc<AtomicBool>,
 ) {
 }
+
+/// Opens the log file in another GUI window, leaving the current game session alone.
+pub fn spawn_log_viewer(log_file_full_path: &std::path::Path) {
+    let result = std::env::current_exe()
+        .and_then(|current_exe| std::process::Command::new(current_exe).arg(log_file_full_path).spawn());
+    if let Err(err) = result {
+        tracing::error!("Failed opening the log viewer: {}", err);
+    }
+}
+
+/// Whether the attached game is still running.
+///
+/// There is no such check offline, the game folder of a log file is only a guess.
+pub fn is_game_running(data: &AppLaunchConfig) -> bool {
+    let sys = sysinfo::System::new_with_specifics(
+        RefreshKind::new().with_processes(ProcessRefreshKind::new())
+    );
+    sys.process(data.target_process_id()).is_some()
+}
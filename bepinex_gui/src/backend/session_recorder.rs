@@ -0,0 +1,194 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, MutexGuard, OnceLock,
+    },
+    thread::{self, JoinHandle},
+    time::SystemTime,
+};
+
+use crossbeam_channel::{Receiver, Sender};
+
+use crate::{
+    config::launch::AppLaunchConfig,
+    data::{
+        bepinex_log::BepInExLogEntry,
+        bepinex_mod::BepInExMod,
+        session_history::{self, SessionInfo, StoredSession},
+    },
+};
+
+/// Keeps what the current game launch logged, for saving it to the session history.
+///
+/// Everything happens in its own thread, saving rewrites the whole zip of the logs.
+pub struct SessionRecorder {
+    session: RecordedSession,
+    // max session count of each save request
+    save_request_sender: Option<Sender<usize>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+/// The session being recorded, for comparing it with the previous one without recording it twice.
+#[derive(Clone)]
+pub struct RecordedSession {
+    current: Arc<Mutex<SessionInfo>>,
+    // bumped when the mods or the error signatures change
+    revision: Arc<AtomicUsize>,
+    // looked up by the recorder thread, reading every saved session takes a while
+    previous: Arc<OnceLock<Option<StoredSession>>>,
+}
+
+impl RecordedSession {
+    pub fn current(&self) -> MutexGuard<'_, SessionInfo> {
+        self.current.lock().unwrap()
+    }
+
+    pub fn revision(&self) -> usize {
+        self.revision.load(Ordering::Acquire)
+    }
+
+    /// `None` while it is still being looked up.
+    pub fn previous(&self) -> Option<Option<&StoredSession>> {
+        self.previous.get().map(Option::as_ref)
+    }
+}
+
+impl SessionRecorder {
+    pub fn new(
+        app_launch_config: &AppLaunchConfig,
+        log_receiver: Receiver<BepInExLogEntry>,
+        mod_receiver: Receiver<BepInExMod>,
+    ) -> Self {
+        let (save_request_sender, save_request_receiver) = crossbeam_channel::unbounded();
+        let session = RecordedSession {
+            current: Arc::new(Mutex::new(SessionInfo::new(app_launch_config))),
+            revision: Arc::default(),
+            previous: Arc::default(),
+        };
+        let mut recording = Recording {
+            session: session.clone(),
+            logs: Vec::new(),
+            has_unsaved_changes: false,
+            has_applied_retention_limit: false,
+        };
+
+        let thread = thread::spawn(move || {
+            let current = recording.session.current().clone();
+            _ = recording
+                .session
+                .previous
+                .set(session_history::find_previous(&current));
+
+            recording.run(&log_receiver, &mod_receiver, &save_request_receiver);
+        });
+
+        Self {
+            session,
+            save_request_sender: Some(save_request_sender),
+            thread: Some(thread),
+        }
+    }
+
+    pub fn session(&self) -> RecordedSession {
+        self.session.clone()
+    }
+
+    /// Writes the session so far in the background, then deletes the sessions past `max_session_count`.
+    pub fn save(&self, max_session_count: usize) {
+        if let Some(save_request_sender) = &self.save_request_sender {
+            _ = save_request_sender.send(max_session_count);
+        }
+    }
+
+    /// Saves one last time, waiting for it so that the session isn't cut short on exit.
+    pub fn finish(&mut self, max_session_count: usize) {
+        self.save(max_session_count);
+        self.save_request_sender = None;
+
+        if let Some(thread) = self.thread.take() {
+            if thread.join().is_err() {
+                tracing::error!("The session recorder thread panicked");
+            }
+        }
+    }
+}
+
+struct Recording {
+    session: RecordedSession,
+    logs: Vec<String>,
+    has_unsaved_changes: bool,
+    // only this session gets added, the others don't need to be listed again
+    has_applied_retention_limit: bool,
+}
+
+impl Recording {
+    // Until the recorder gets finished
+    fn run(
+        &mut self,
+        log_receiver: &Receiver<BepInExLogEntry>,
+        mod_receiver: &Receiver<BepInExMod>,
+        save_request_receiver: &Receiver<usize>,
+    ) {
+        // a disconnected receiver would be selected again and again
+        let never_log = crossbeam_channel::never();
+        let never_mod = crossbeam_channel::never();
+        let mut log_receiver = log_receiver;
+        let mut mod_receiver = mod_receiver;
+
+        loop {
+            crossbeam_channel::select! {
+                recv(log_receiver) -> log => match log {
+                    Ok(log) => {
+                        let mut info = self.session.current();
+                        let error_signature_count = info.error_signatures.len();
+                        info.add_log(&log);
+                        if info.error_signatures.len() != error_signature_count {
+                            self.session.revision.fetch_add(1, Ordering::AcqRel);
+                        }
+                        drop(info);
+
+                        self.logs.push(log.data().to_string());
+                        self.has_unsaved_changes = true;
+                    }
+                    Err(_) => log_receiver = &never_log,
+                },
+                recv(mod_receiver) -> mod_ => match mod_ {
+                    Ok(mod_) => {
+                        self.session.current().add_mod(mod_);
+                        self.session.revision.fetch_add(1, Ordering::AcqRel);
+                        self.has_unsaved_changes = true;
+                    }
+                    Err(_) => mod_receiver = &never_mod,
+                },
+                recv(save_request_receiver) -> max_session_count => match max_session_count {
+                    Ok(max_session_count) => self.save(max_session_count),
+                    Err(_) => return,
+                },
+            }
+        }
+    }
+
+    fn save(&mut self, max_session_count: usize) {
+        if !self.has_unsaved_changes {
+            return;
+        }
+
+        // not holding the lock while zipping, the General tab reads it every frame
+        let info = {
+            let mut info = self.session.current();
+            info.end_time = SystemTime::now();
+            info.clone()
+        };
+
+        match session_history::save(&info, self.logs.iter().map(String::as_str)) {
+            Ok(()) => {
+                self.has_unsaved_changes = false;
+                if !self.has_applied_retention_limit {
+                    session_history::apply_retention_limit(max_session_count);
+                    self.has_applied_retention_limit = true;
+                }
+            }
+            Err(err) => tracing::error!("Failed saving session: {}", err),
+        }
+    }
+}
@@ -0,0 +1,257 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::PathBuf,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crossbeam_channel::Receiver;
+use serde::{Deserialize, Serialize};
+
+use crate::paths;
+
+pub const DEFAULT_BASE_URL: &str = "https://thunderstore.io";
+
+const CACHE_FILE_NAME: &str = "thunderstore_packages.json";
+
+/// What Thunderstore knows about an installed package.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageInfo {
+    pub full_name: String,
+    pub package_url: String,
+    pub is_deprecated: bool,
+    pub latest_version: String,
+}
+
+impl PackageInfo {
+    pub fn is_newer_than(&self, installed_version: &str) -> bool {
+        parse_version(&self.latest_version) > parse_version(installed_version)
+    }
+}
+
+// `/api/experimental/package/{namespace}/{name}/`, only what is shown
+#[derive(Deserialize)]
+struct PackageResponse {
+    full_name: String,
+    package_url: String,
+    is_deprecated: bool,
+    latest: PackageVersionResponse,
+}
+
+#[derive(Deserialize)]
+struct PackageVersionResponse {
+    version_number: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    fetched_time: SystemTime,
+    // `None` when the package isn't on Thunderstore
+    package: Option<PackageInfo>,
+}
+
+/// Looks up the packages on Thunderstore in the background.
+///
+/// `package_ids` are `Namespace-Name`, the results come as `(package id, info)` in the receiver.
+/// Lookups younger than `cache_ttl` are taken from the disk cache instead.
+/// A blank `base_url` means `DEFAULT_BASE_URL`.
+pub fn spawn_lookup(
+    base_url: &str,
+    cache_ttl: Duration,
+    package_ids: Vec<String>,
+) -> Receiver<(String, PackageInfo)> {
+    let (package_s, package_r) = crossbeam_channel::unbounded();
+    let base_url = base_url_or_default(base_url);
+
+    thread::spawn(move || {
+        let mut cache = read_cache();
+        let now = SystemTime::now();
+
+        for package_id in package_ids {
+            let is_fresh = |entry: &CacheEntry| {
+                now.duration_since(entry.fetched_time)
+                    .is_ok_and(|age| age < cache_ttl)
+            };
+
+            let cache_key = cache_key(&base_url, &package_id);
+            let package = match cache.get(&cache_key).filter(|entry| is_fresh(entry)) {
+                Some(entry) => entry.package.clone(),
+                None => match fetch_package(&base_url, &package_id) {
+                    Ok(package) => {
+                        cache.insert(
+                            cache_key,
+                            CacheEntry {
+                                fetched_time: now,
+                                package: package.clone(),
+                            },
+                        );
+                        package
+                    }
+                    Err(err) => {
+                        tracing::warn!("Failed looking up {} on Thunderstore: {}", package_id, err);
+                        continue;
+                    }
+                },
+            };
+
+            if let Some(package) = package {
+                if package_s.send((package_id, package)).is_err() {
+                    break;
+                }
+            }
+        }
+
+        write_cache(&cache);
+    });
+
+    package_r
+}
+
+fn fetch_package(
+    base_url: &str,
+    package_id: &str,
+) -> Result<Option<PackageInfo>, Box<dyn std::error::Error>> {
+    let (namespace, name) = package_id
+        .split_once('-')
+        .ok_or("package id isn't Namespace-Name")?;
+
+    let response = reqwest::blocking::get(format!(
+        "{base_url}/api/experimental/package/{namespace}/{name}/"
+    ))?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+
+    let package = serde_json::from_str::<PackageResponse>(&response.error_for_status()?.text()?)?;
+
+    Ok(Some(PackageInfo {
+        full_name: package.full_name,
+        package_url: package.package_url,
+        is_deprecated: package.is_deprecated,
+        latest_version: package.latest.version_number,
+    }))
+}
+
+fn base_url_or_default(base_url: &str) -> String {
+    let base_url = base_url.trim().trim_end_matches('/');
+    match base_url.is_empty() {
+        true => DEFAULT_BASE_URL.to_string(),
+        false => base_url.to_string(),
+    }
+}
+
+// `1.10.0` is newer than `1.9.2`, non numeric parts count as 0
+fn parse_version(version: &str) -> Vec<u64> {
+    let mut parts: Vec<u64> = version
+        .split('.')
+        .map(|part| part.trim().parse().unwrap_or(0))
+        .collect();
+
+    // `1.2` is the same as `1.2.0`
+    while parts.last() == Some(&0) {
+        parts.pop();
+    }
+
+    parts
+}
+
+// The same package can differ between the Thunderstore instances, or not be on one of them
+fn cache_key(base_url: &str, package_id: &str) -> String {
+    format!("{base_url} {package_id}")
+}
+
+fn cache_file_full_path() -> Option<PathBuf> {
+    paths::get_app_config_directory().map(|directory| directory.join(CACHE_FILE_NAME))
+}
+
+fn read_cache() -> HashMap<String, CacheEntry> {
+    cache_file_full_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+fn write_cache(cache: &HashMap<String, CacheEntry>) {
+    let Some(path) = cache_file_full_path() else {
+        return;
+    };
+
+    let result = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| {
+            serde_json::to_string(cache)
+                .map_err(std::io::Error::from)
+                .and_then(|json| fs::write(&path, json))
+        });
+    if let Err(err) = result {
+        tracing::error!("Failed writing {:?}: {}", path, err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_version_compares_numerically() {
+        assert!(parse_version("1.10.0") > parse_version("1.9.2"));
+        assert!(parse_version("2.0.0") > parse_version("1.99.99"));
+        assert!(parse_version("1.0.1") > parse_version("1.0.0"));
+    }
+
+    #[test]
+    fn parse_version_ignores_trailing_zeros() {
+        assert_eq!(parse_version("1.2"), parse_version("1.2.0"));
+        assert_eq!(parse_version(" 1 . 2 . 3 "), vec![1, 2, 3]);
+        assert_eq!(parse_version("0.0.0"), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn parse_version_counts_non_numeric_parts_as_zero() {
+        assert_eq!(parse_version("1.beta.3"), vec![1, 0, 3]);
+        assert_eq!(parse_version(""), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn is_newer_than_uses_the_latest_version() {
+        let package = PackageInfo {
+            full_name: "Author-Mod".to_string(),
+            package_url: String::new(),
+            is_deprecated: false,
+            latest_version: "1.10.0".to_string(),
+        };
+
+        assert!(package.is_newer_than("1.9.0"));
+        assert!(!package.is_newer_than("1.10"));
+        assert!(!package.is_newer_than("1.10.1"));
+    }
+
+    #[test]
+    fn cache_key_differs_between_instances() {
+        assert_eq!(
+            cache_key("https://thunderstore.io", "Author-Mod"),
+            "https://thunderstore.io Author-Mod"
+        );
+        assert_ne!(
+            cache_key("https://thunderstore.io", "Author-Mod"),
+            cache_key("http://localhost:8000", "Author-Mod")
+        );
+        assert_ne!(
+            cache_key("https://thunderstore.io", "Author-Mod"),
+            cache_key("https://thunderstore.io", "Author-Other")
+        );
+    }
+
+    #[test]
+    fn base_url_falls_back_to_the_default_when_blank() {
+        assert_eq!(base_url_or_default(""), DEFAULT_BASE_URL);
+        assert_eq!(base_url_or_default("   "), DEFAULT_BASE_URL);
+        assert_eq!(base_url_or_default("/"), DEFAULT_BASE_URL);
+        assert_eq!(
+            base_url_or_default(" http://localhost:8000/ "),
+            "http://localhost:8000"
+        );
+    }
+}
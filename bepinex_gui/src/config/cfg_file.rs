@@ -0,0 +1,430 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Values of a BepInEx `.cfg` file, keyed `[Section] Name`. Comments are left out.
+pub fn read_values(cfg_file_full_path: &Path) -> io::Result<BTreeMap<String, String>> {
+    Ok(CfgFile::read(cfg_file_full_path)?
+        .entries
+        .into_iter()
+        .map(|entry| (format!("[{}] {}", entry.section, entry.name), entry.value))
+        .collect())
+}
+
+/// `.cfg` files of the folder and its subfolders, sorted.
+pub fn find_cfg_files(directory: &Path) -> Vec<PathBuf> {
+    let mut cfg_files = Vec::new();
+    find_cfg_files_in(directory, &mut cfg_files);
+    cfg_files.sort();
+
+    cfg_files
+}
+
+fn find_cfg_files_in(folder: &Path, cfg_files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(folder) else {
+        return;
+    };
+
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.is_dir() {
+            find_cfg_files_in(&path, cfg_files);
+        } else if path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("cfg"))
+        {
+            cfg_files.push(path);
+        }
+    }
+}
+
+/// What the `# Setting type` comment tells about a setting, for picking its widget.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CfgValueKind {
+    Boolean,
+    Integer,
+    // `Byte` and the `UInt` types, `UInt64` goes past what `Int64` can hold
+    UnsignedInteger,
+    Float,
+    // `# Acceptable values`, one of them
+    Enum(Vec<String>),
+    // `[Flags]` enums, any of them separated by `, `
+    Flags(Vec<String>),
+    Text,
+}
+
+/// A `Name = value` line, with the comments BepInEx writes above it.
+#[derive(Debug, Clone)]
+pub struct CfgEntry {
+    pub section: String,
+    pub name: String,
+    pub value: String,
+    // the `##` lines
+    pub description: String,
+    pub setting_type: Option<String>,
+    pub default_value: Option<String>,
+    pub kind: CfgValueKind,
+    // `# Acceptable value range: From 0 to 100`
+    pub range: Option<(f64, f64)>,
+    line_index: usize,
+    // `Name = `, kept as is when writing the value back
+    value_prefix: String,
+    // what is in the file, the line is only rewritten when the value changed
+    saved_value: String,
+}
+
+impl CfgEntry {
+    pub fn is_changed(&self) -> bool {
+        self.value != self.saved_value
+    }
+
+    /// Why BepInEx would reject the value, `None` when it is fine.
+    pub fn validate(&self, value: &str) -> Option<String> {
+        let value = value.trim();
+        match &self.kind {
+            CfgValueKind::Boolean => (!value.eq_ignore_ascii_case("true")
+                && !value.eq_ignore_ascii_case("false"))
+            .then(|| "Must be true or false".to_string()),
+            CfgValueKind::Integer => match value.parse::<i64>() {
+                Ok(number) => self.validate_range(number as f64),
+                Err(_) => Some("Must be a whole number".to_string()),
+            },
+            CfgValueKind::UnsignedInteger => match value.parse::<u64>() {
+                Ok(number) => self.validate_range(number as f64),
+                Err(_) => Some("Must be a whole number, 0 or more".to_string()),
+            },
+            // the configs are written with the invariant culture, `.` for decimals
+            CfgValueKind::Float => match value.parse::<f64>() {
+                Ok(number) => self.validate_range(number),
+                Err(_) => Some("Must be a number, with a . for decimals".to_string()),
+            },
+            CfgValueKind::Enum(acceptable_values) => (!acceptable_values
+                .iter()
+                .any(|acceptable_value| acceptable_value == value))
+            .then(|| format!("Must be one of {}", acceptable_values.join(", "))),
+            CfgValueKind::Flags(acceptable_values) => value
+                .split(',')
+                .map(str::trim)
+                .find(|flag| {
+                    !acceptable_values
+                        .iter()
+                        .any(|acceptable_value| acceptable_value == flag)
+                })
+                .map(|flag| format!("{flag} is not one of {}", acceptable_values.join(", "))),
+            CfgValueKind::Text => None,
+        }
+    }
+
+    fn validate_range(&self, number: f64) -> Option<String> {
+        let (min, max) = self.range?;
+        (number < min || number > max).then(|| format!("Must be from {min} to {max}"))
+    }
+}
+
+/// A BepInEx `.cfg` file that can be edited and written back.
+///
+/// Only the lines of the edited values change, comments, blank lines and ordering are kept.
+#[derive(Debug, Clone)]
+pub struct CfgFile {
+    pub path: PathBuf,
+    pub entries: Vec<CfgEntry>,
+    lines: Vec<String>,
+    line_ending: &'static str,
+    has_trailing_line_ending: bool,
+    has_bom: bool,
+}
+
+impl CfgFile {
+    pub fn read(cfg_file_full_path: &Path) -> io::Result<Self> {
+        let text = fs::read_to_string(cfg_file_full_path)?;
+        Ok(Self::parse(cfg_file_full_path, &text))
+    }
+
+    pub fn parse(cfg_file_full_path: &Path, text: &str) -> Self {
+        // BepInEx writes a BOM
+        let has_bom = text.starts_with('\u{FEFF}');
+        let text = text.trim_start_matches('\u{FEFF}');
+        let lines: Vec<String> = text.lines().map(str::to_string).collect();
+
+        let mut entries = Vec::new();
+        let mut section = String::new();
+        let mut comments = EntryComments::default();
+        for (line_index, line) in lines.iter().enumerate() {
+            let trimmed_line = line.trim();
+
+            if let Some(description_line) = trimmed_line.strip_prefix("##") {
+                comments
+                    .description
+                    .push(description_line.trim().to_string());
+            } else if let Some(comment) = trimmed_line.strip_prefix('#') {
+                comments.add(comment.trim());
+            } else if let Some(section_name) = trimmed_line
+                .strip_prefix('[')
+                .and_then(|l| l.strip_suffix(']'))
+            {
+                section = section_name.to_string();
+                comments = EntryComments::default();
+            } else if let Some((name, value)) = line.split_once('=') {
+                let value_start = line.len() - value.trim_start().len();
+                entries.push(std::mem::take(&mut comments).into_entry(
+                    &section,
+                    name.trim(),
+                    value.trim(),
+                    line_index,
+                    &line[..value_start],
+                ));
+            }
+        }
+
+        Self {
+            path: cfg_file_full_path.to_path_buf(),
+            entries,
+            line_ending: if text.contains("\r\n") { "\r\n" } else { "\n" },
+            has_trailing_line_ending: text.ends_with('\n'),
+            has_bom,
+            lines,
+        }
+    }
+
+    /// Sections in the order of the file, with the indices of their entries.
+    pub fn sections(&self) -> Vec<(&str, Vec<usize>)> {
+        let mut sections: Vec<(&str, Vec<usize>)> = Vec::new();
+        for (entry_index, entry) in self.entries.iter().enumerate() {
+            match sections.last_mut() {
+                Some((section, entry_indices)) if *section == entry.section => {
+                    entry_indices.push(entry_index);
+                }
+                _ => sections.push((&entry.section, vec![entry_index])),
+            }
+        }
+
+        sections
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut lines = self.lines.clone();
+        for entry in self.entries.iter().filter(|entry| entry.is_changed()) {
+            lines[entry.line_index] = format!("{}{}", entry.value_prefix, entry.value);
+        }
+
+        let mut text = match self.has_bom {
+            true => "\u{FEFF}".to_string(),
+            false => String::new(),
+        };
+        text += &lines.join(self.line_ending);
+        if self.has_trailing_line_ending {
+            text += self.line_ending;
+        }
+
+        text
+    }
+
+    pub fn is_changed(&self) -> bool {
+        self.entries.iter().any(CfgEntry::is_changed)
+    }
+
+    /// Writes the changed values over the file as it is now, the mods can save it after it got read.
+    ///
+    /// Fails without writing anything when a changed setting isn't in the file anymore.
+    pub fn write(&mut self) -> io::Result<()> {
+        let mut cfg_file = Self::read(&self.path)?;
+        for entry in self.entries.iter().filter(|entry| entry.is_changed()) {
+            let Some(file_entry) = cfg_file.entries.iter_mut().find(|file_entry| {
+                file_entry.section == entry.section && file_entry.name == entry.name
+            }) else {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!(
+                        "[{}] {} isn't in the file anymore, discard the changes to reload it",
+                        entry.section, entry.name
+                    ),
+                ));
+            };
+            file_entry.value = entry.value.clone();
+        }
+
+        fs::write(&self.path, cfg_file.to_text())?;
+
+        for entry in &mut cfg_file.entries {
+            entry.saved_value = entry.value.clone();
+        }
+        *self = cfg_file;
+
+        Ok(())
+    }
+}
+
+// Comments above the entry being parsed
+#[derive(Default)]
+struct EntryComments {
+    description: Vec<String>,
+    setting_type: Option<String>,
+    default_value: Option<String>,
+    acceptable_values: Option<Vec<String>>,
+    range: Option<(f64, f64)>,
+    is_flags: bool,
+}
+
+impl EntryComments {
+    fn add(&mut self, comment: &str) {
+        if let Some(setting_type) = comment.strip_prefix("Setting type:") {
+            self.setting_type = Some(setting_type.trim().to_string());
+        } else if let Some(default_value) = comment.strip_prefix("Default value:") {
+            self.default_value = Some(default_value.trim().to_string());
+        } else if let Some(acceptable_values) = comment.strip_prefix("Acceptable values:") {
+            self.acceptable_values = Some(
+                acceptable_values
+                    .split(',')
+                    .map(|acceptable_value| acceptable_value.trim().to_string())
+                    .collect(),
+            );
+        } else if let Some(range) = comment.strip_prefix("Acceptable value range: From ") {
+            self.range = range
+                .split_once(" to ")
+                .and_then(|(min, max)| Some((min.trim().parse().ok()?, max.trim().parse().ok()?)));
+        } else if comment.starts_with("Multiple values can be set at the same time") {
+            self.is_flags = true;
+        }
+    }
+
+    fn into_entry(
+        self,
+        section: &str,
+        name: &str,
+        value: &str,
+        line_index: usize,
+        value_prefix: &str,
+    ) -> CfgEntry {
+        let kind = match (self.acceptable_values, self.setting_type.as_deref()) {
+            (Some(acceptable_values), _) if self.is_flags => CfgValueKind::Flags(acceptable_values),
+            (Some(acceptable_values), _) => CfgValueKind::Enum(acceptable_values),
+            (None, Some("Boolean")) => CfgValueKind::Boolean,
+            (None, Some("SByte" | "Int16" | "Int32" | "Int64")) => CfgValueKind::Integer,
+            (None, Some("Byte" | "UInt16" | "UInt32" | "UInt64")) => CfgValueKind::UnsignedInteger,
+            (None, Some("Single" | "Double" | "Decimal")) => CfgValueKind::Float,
+            (None, _) => CfgValueKind::Text,
+        };
+
+        CfgEntry {
+            section: section.to_string(),
+            name: name.to_string(),
+            value: value.to_string(),
+            saved_value: value.to_string(),
+            description: self.description.join("\n"),
+            setting_type: self.setting_type,
+            default_value: self.default_value,
+            kind,
+            range: self.range,
+            line_index,
+            value_prefix: value_prefix.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // As BepInEx writes it, with a BOM and `\r\n`
+    const CFG_TEXT: &str =
+        "\u{FEFF}## Settings file was created by plugin Risk Of Options v2.7.1\r\n\
+## Plugin GUID: com.rune580.riskofoptions\r\n\
+\r\n\
+[General]\r\n\
+\r\n\
+## Whether the options show in the pause menu\r\n\
+# Setting type: Boolean\r\n\
+# Default value: true\r\n\
+Enabled = true\r\n\
+\r\n\
+## How fast the menus open\r\n\
+# Setting type: Single\r\n\
+# Default value: 1.5\r\n\
+# Acceptable value range: From 0 to 10\r\n\
+Speed = 1.5\r\n\
+\r\n\
+[Logging]\r\n\
+\r\n\
+## Size of the log file, in kilobytes\r\n\
+# Setting type: UInt64\r\n\
+# Default value: 18446744073709551615\r\n\
+Max Size = 18446744073709551615\r\n";
+
+    fn parse(text: &str) -> CfgFile {
+        CfgFile::parse(Path::new("com.rune580.riskofoptions.cfg"), text)
+    }
+
+    #[test]
+    fn unchanged_file_is_written_back_as_is() {
+        assert_eq!(parse(CFG_TEXT).to_text(), CFG_TEXT);
+
+        for text in [
+            "[General]\nEnabled = true",
+            "[General]\nEnabled = true\n",
+            "\u{FEFF}[General]\r\nEnabled = true",
+        ] {
+            assert_eq!(parse(text).to_text(), text);
+        }
+    }
+
+    #[test]
+    fn editing_a_value_only_changes_its_line() {
+        let mut cfg_file = parse(CFG_TEXT);
+        cfg_file.entries[1].value = "2.5".to_string();
+
+        assert!(cfg_file.is_changed());
+        assert_eq!(
+            cfg_file.to_text(),
+            CFG_TEXT.replace("Speed = 1.5\r\n", "Speed = 2.5\r\n")
+        );
+    }
+
+    #[test]
+    fn writing_keeps_what_got_saved_since_reading() {
+        let path = std::env::temp_dir().join("bepinex_gui_cfg_file_test.cfg");
+        fs::write(&path, CFG_TEXT).unwrap();
+
+        let mut cfg_file = CfgFile::read(&path).unwrap();
+        cfg_file.entries[1].value = "2.5".to_string();
+        // the game closing saves the file again
+        let saved_text = CFG_TEXT.replace("Enabled = true", "Enabled = false");
+        fs::write(&path, &saved_text).unwrap();
+        cfg_file.write().unwrap();
+
+        let written_text = fs::read_to_string(&path).unwrap();
+        _ = fs::remove_file(&path);
+        assert_eq!(
+            written_text,
+            saved_text.replace("Speed = 1.5", "Speed = 2.5")
+        );
+        assert!(!cfg_file.is_changed());
+        assert_eq!(cfg_file.entries[0].value, "false");
+    }
+
+    #[test]
+    fn entries_keep_their_section_and_comments() {
+        let cfg_file = parse(CFG_TEXT);
+
+        assert_eq!(
+            cfg_file.sections(),
+            vec![("General", vec![0, 1]), ("Logging", vec![2])]
+        );
+        assert_eq!(cfg_file.entries[0].kind, CfgValueKind::Boolean);
+        assert_eq!(cfg_file.entries[1].description, "How fast the menus open");
+        assert_eq!(cfg_file.entries[1].range, Some((0., 10.)));
+        assert_eq!(cfg_file.entries[2].kind, CfgValueKind::UnsignedInteger);
+    }
+
+    #[test]
+    fn validates_like_bepinex() {
+        let cfg_file = parse(CFG_TEXT);
+
+        assert_eq!(cfg_file.entries[0].validate("False"), None);
+        assert!(cfg_file.entries[0].validate("yes").is_some());
+        assert_eq!(cfg_file.entries[1].validate("10"), None);
+        assert!(cfg_file.entries[1].validate("10.5").is_some());
+        assert_eq!(cfg_file.entries[2].validate("18446744073709551615"), None);
+        assert!(cfg_file.entries[2].validate("-1").is_some());
+    }
+}
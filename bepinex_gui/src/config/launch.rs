@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use sysinfo::Pid;
 
@@ -7,6 +7,7 @@ use crate::app;
 #[derive(Debug, Clone)]
 pub struct AppLaunchConfig {
     target_name: String,
+    bepinex_version: String,
     game_folder_full_path: PathBuf,
     bepinex_log_output_file_full_path: PathBuf,
     bepinex_gui_csharp_cfg_full_path: PathBuf,
@@ -14,24 +15,32 @@ pub struct AppLaunchConfig {
     // Socket port used for comm with the bep gui patcher
     log_socket_port_receiver: u16,
     window_title: String,
+    // Viewing a log file, no game attached
+    is_offline: bool,
 }
 
 impl AppLaunchConfig {
     const ARG_COUNT: usize = 8;
+    // `bepinex_gui path/to/LogOutput.log`
+    const OFFLINE_ARG_COUNT: usize = 2;
 
     pub fn from(args: &Vec<String>) -> Option<Self> {
-        if args.len() == Self::ARG_COUNT {
+        if args.len() == Self::OFFLINE_ARG_COUNT {
+            Some(Self::offline(Some((&args[1]).into())))
+        } else if args.len() == Self::ARG_COUNT {
             let bepinex_version = &args[1];
             let target_name = &args[2];
 
             Some(Self {
                 target_name: target_name.into(),
+                bepinex_version: bepinex_version.into(),
                 game_folder_full_path: (&args[3]).into(),
                 bepinex_log_output_file_full_path: (&args[4]).into(),
                 bepinex_gui_csharp_cfg_full_path: (&args[5]).into(),
                 target_process_id: args[6].parse::<Pid>().unwrap(),
                 log_socket_port_receiver: args[7].parse::<u16>().unwrap(),
                 window_title: Self::format_window_title(bepinex_version, target_name),
+                is_offline: false,
             })
         } else {
             tracing::error!("Problem with args {:?} {:?}", args.len(), args);
@@ -40,10 +49,66 @@ impl AppLaunchConfig {
         }
     }
 
+    /// For viewing a `LogOutput.log`, `None` until the user picks one.
+    pub fn offline(log_file_full_path: Option<PathBuf>) -> Self {
+        let log_file_full_path = log_file_full_path.unwrap_or_default();
+
+        // only a log where BepInEx writes it tells which game it is from,
+        // the tabs changing files stay empty for the copies of a log
+        let game_folder_full_path = Self::game_folder_of_log_file(&log_file_full_path).unwrap_or_default();
+
+        let window_title = match log_file_full_path.file_name() {
+            Some(file_name) => format!("{} - {} (offline)", app::NAME, file_name.to_string_lossy()),
+            None => format!("{} - Log viewer", app::NAME),
+        };
+
+        Self {
+            target_name: String::new(),
+            bepinex_version: String::new(),
+            game_folder_full_path,
+            bepinex_log_output_file_full_path: log_file_full_path,
+            // left empty so that the GUI settings of the game aren't touched
+            bepinex_gui_csharp_cfg_full_path: PathBuf::new(),
+            target_process_id: Pid::from(0),
+            log_socket_port_receiver: 0,
+            window_title,
+            is_offline: true,
+        }
+    }
+
+    // `Game/BepInEx/LogOutput.log`
+    fn game_folder_of_log_file(log_file_full_path: &Path) -> Option<PathBuf> {
+        let is_named = |path: &Path, name: &str| {
+            path.file_name()
+                .is_some_and(|file_name| file_name.to_string_lossy().eq_ignore_ascii_case(name))
+        };
+
+        let bepinex_folder = log_file_full_path.parent()?;
+        if !is_named(log_file_full_path, "LogOutput.log") || !is_named(bepinex_folder, "BepInEx") {
+            return None;
+        }
+
+        bepinex_folder.parent().map(PathBuf::from)
+    }
+
+    pub const fn is_offline(&self) -> bool {
+        self.is_offline
+    }
+
+    /// The log file being viewed, when offline.
+    pub fn offline_log_file(&self) -> Option<&PathBuf> {
+        (self.is_offline && !self.bepinex_log_output_file_full_path.as_os_str().is_empty())
+            .then_some(&self.bepinex_log_output_file_full_path)
+    }
+
     pub fn target_name(&self) -> &str {
         self.target_name.as_ref()
     }
 
+    pub fn bepinex_version(&self) -> &str {
+        self.bepinex_version.as_ref()
+    }
+
     pub const fn game_folder_full_path(&self) -> &PathBuf {
         &self.game_folder_full_path
     }
@@ -52,6 +117,19 @@ impl AppLaunchConfig {
         &self.bepinex_log_output_file_full_path
     }
 
+    /// The `BepInEx` folder the game loads from, outside of the game folder for mod manager profiles.
+    /// Empty offline when the log file isn't in a game folder.
+    pub fn bepinex_root(&self) -> PathBuf {
+        if self.is_offline && self.game_folder_full_path.as_os_str().is_empty() {
+            return PathBuf::new();
+        }
+
+        // `BepInEx/LogOutput.log`
+        self.bepinex_log_output_file_full_path
+            .parent()
+            .map_or_else(|| self.game_folder_full_path.join("BepInEx"), PathBuf::from)
+    }
+
     pub const fn bepinex_gui_csharp_cfg_full_path(&self) -> &PathBuf {
         &self.bepinex_gui_csharp_cfg_full_path
     }
@@ -74,17 +152,6 @@ impl AppLaunchConfig {
 
 impl Default for AppLaunchConfig {
     fn default() -> Self {
-        let bepinex_version = "5.4.19";
-        let target_name = "Risk of Rain 2";
-
-        Self {
-            target_name : target_name.into(),
-            game_folder_full_path: "C:\\Program Files (x86)\\Steam\\steamapps\\common\\Risk of Rain 2".into(),
-            bepinex_log_output_file_full_path: "C:\\Program Files (x86)\\Steam\\steamapps\\common\\Risk of Rain 2\\BepInEx\\LogOutput.log".into(),
-            bepinex_gui_csharp_cfg_full_path: "C:\\Program Files (x86)\\Steam\\steamapps\\common\\Risk of Rain 2\\BepInEx\\config\\BepInEx.GUI.cfg".into(),
-            target_process_id: Pid::from(17584),
-            log_socket_port_receiver: 27090,
-            window_title : Self::format_window_title(bepinex_version, target_name),
-        }
+        Self::offline(None)
     }
 }
@@ -1,4 +1,5 @@
 use std::{
+    collections::HashMap,
     fs::File,
     io::{self, BufRead, BufReader, Seek, SeekFrom, Write},
     path::PathBuf,
@@ -8,18 +9,40 @@ use std::{
     },
 };
 
-use eframe::egui::Context;
+use eframe::{egui::Context, epaint::Color32};
 use serde::*;
 
-use crate::{app, data::bepinex_log::LogLevel};
+use crate::{
+    app,
+    backend::{
+        alerts::{self, AlertTrigger},
+        thunderstore::package_index,
+    },
+    data::bepinex_log::{rules::LogRules, LogLevel},
+    theme::{self, LogColorSlot, LogColors},
+};
 
+pub mod cfg_file;
 pub mod launch;
 
 #[derive(Serialize, Deserialize)]
+#[serde(default)]
 pub struct Config {
     #[serde(skip)]
     pub theme_just_changed: bool,
 
+    // Picked or dropped by the user, opened at the start of the next frame
+    #[serde(skip)]
+    pub log_file_to_open: Option<PathBuf>,
+
+    // Mod name picked in the General tab, the console gets shown filtered to it
+    #[serde(skip)]
+    pub console_filter_to_open: Option<String>,
+
+    // Set when a profile got applied, for the General tab to scan the plugins again
+    #[serde(skip)]
+    pub mods_folder_changed: bool,
+
     pub font_size: f32,
     
     pub dark_mode: bool,
@@ -33,8 +56,34 @@ pub struct Config {
     // For remembering the last selected tab
     pub selected_tab_index: usize,
 
-    // For remembering the selected log level filter (Console tab)
-    pub log_level_filter: LogLevel,
+    // Console panes, shown side by side
+    pub console_panes: Vec<ConsolePaneConfig>,
+
+    // Console colors picked by the user, on top of the theme ones
+    pub log_color_overrides_dark: HashMap<LogColorSlot, Color32>,
+    pub log_color_overrides_light: HashMap<LogColorSlot, Color32>,
+
+    // Highlight / reclassification rules of the console
+    pub log_rules: LogRules,
+
+    // When to notify the user about new logs
+    pub alert_triggers: Vec<AlertTrigger>,
+
+    // Commands sent from the console, oldest first
+    pub command_history: Vec<String>,
+
+    // Past sessions kept on disk, the oldest ones get deleted
+    pub session_history_limit: usize,
+
+    // Latest versions of the installed packages, see `thunderstore::package_index`
+    pub check_mod_updates: bool,
+    // can point to a local mirror
+    pub thunderstore_base_url: String,
+    pub thunderstore_cache_ttl_hours: u64,
+
+    // Theme colors + overrides, see `update_log_colors`
+    #[serde(skip)]
+    pub log_colors: LogColors,
 
     // Skipped because those fields are saved through the regular bepinex config system
     #[serde(skip)]
@@ -51,12 +100,25 @@ impl Default for Config {
     fn default() -> Self {
         Self {
             theme_just_changed: true,
+            log_file_to_open: None,
+            console_filter_to_open: None,
+            mods_folder_changed: false,
             font_size: 20.0,
             dark_mode: true,
             first_time: true,
             first_time_console_disclaimer: true,
             selected_tab_index: 0,
-            log_level_filter: LogLevel::All,
+            console_panes: vec![ConsolePaneConfig::default()],
+            log_color_overrides_dark: HashMap::new(),
+            log_color_overrides_light: HashMap::new(),
+            log_colors: theme::get_log_colors(true),
+            log_rules: LogRules::default(),
+            alert_triggers: alerts::default_triggers(),
+            command_history: Vec::new(),
+            session_history_limit: 20,
+            check_mod_updates: true,
+            thunderstore_base_url: package_index::DEFAULT_BASE_URL.to_string(),
+            thunderstore_cache_ttl_hours: 6,
             close_window_when_game_loaded: false,
             close_window_when_game_closes: Arc::new(AtomicBool::new(true)),
             bepinex_gui_csharp_cfg_full_path: Default::default(),
@@ -64,6 +126,25 @@ impl Default for Config {
     }
 }
 
+/// Persisted part of a console pane, its scroll and selection only live for the session.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConsolePaneConfig {
+    pub filter_text: String,
+
+    // For remembering the selected log level filter
+    pub log_level_filter: LogLevel,
+}
+
+impl Default for ConsolePaneConfig {
+    fn default() -> Self {
+        Self {
+            filter_text: String::new(),
+            log_level_filter: LogLevel::All,
+        }
+    }
+}
+
 impl Config {
     pub fn read_bepinex_toml_cfg_file(&mut self) -> io::Result<()> {
         let file = File::open(&self.bepinex_gui_csharp_cfg_full_path)?;
@@ -152,6 +233,27 @@ impl Config {
         Ok(())
     }
 
+    pub fn log_color_overrides_mut(&mut self) -> &mut HashMap<LogColorSlot, Color32> {
+        if self.dark_mode {
+            &mut self.log_color_overrides_dark
+        } else {
+            &mut self.log_color_overrides_light
+        }
+    }
+
+    pub fn update_log_colors(&mut self) {
+        self.log_colors = theme::get_log_colors(self.dark_mode);
+
+        let overrides = if self.dark_mode {
+            &self.log_color_overrides_dark
+        } else {
+            &self.log_color_overrides_light
+        };
+        for (slot, color) in overrides {
+            self.log_colors.set(*slot, *color);
+        }
+    }
+
     pub fn update_text_styles(&mut self, ctx: &Context) {
         use eframe::egui::{TextStyle::*, FontId};
         use eframe::epaint::FontFamily;
@@ -0,0 +1,279 @@
+use std::ops::Range;
+
+use eframe::epaint::Color32;
+
+const ESC: char = '\u{1b}';
+
+// Same palette as the windows terminal "Campbell" scheme
+const PALETTE: [Color32; 16] = [
+    Color32::from_rgb(12, 12, 12),
+    Color32::from_rgb(197, 15, 31),
+    Color32::from_rgb(19, 161, 14),
+    Color32::from_rgb(193, 156, 0),
+    Color32::from_rgb(0, 55, 218),
+    Color32::from_rgb(136, 23, 152),
+    Color32::from_rgb(58, 150, 221),
+    Color32::from_rgb(204, 204, 204),
+    Color32::from_rgb(118, 118, 118),
+    Color32::from_rgb(231, 72, 86),
+    Color32::from_rgb(22, 198, 12),
+    Color32::from_rgb(249, 241, 165),
+    Color32::from_rgb(59, 120, 255),
+    Color32::from_rgb(180, 0, 158),
+    Color32::from_rgb(97, 214, 214),
+    Color32::from_rgb(242, 242, 242),
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AnsiStyle {
+    pub foreground: Option<Color32>,
+    pub background: Option<Color32>,
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnsiSpan {
+    /// Byte range in the stripped text.
+    pub range: Range<usize>,
+    pub style: AnsiStyle,
+}
+
+/// Returns `text` without its escape sequences,
+/// and the styled spans if `text` had any SGR (colour / bold / underline...) sequence.
+pub fn parse(text: &str) -> (String, Option<Vec<AnsiSpan>>) {
+    if !text.contains(ESC) {
+        return (text.to_string(), None);
+    }
+
+    let mut stripped = String::with_capacity(text.len());
+    let mut spans: Vec<AnsiSpan> = Vec::new();
+    let mut style = AnsiStyle::default();
+    let mut span_start = 0;
+
+    let mut chars = text.char_indices().peekable();
+    while let Some((_, c)) = chars.next() {
+        if c != ESC {
+            stripped.push(c);
+            continue;
+        }
+
+        // only CSI sequences (`ESC [ params final_byte`) are supported, lone escapes are dropped
+        if chars.peek().map(|(_, c)| *c) != Some('[') {
+            continue;
+        }
+        chars.next();
+
+        let mut params = String::new();
+        let mut final_byte = None;
+        for (_, c) in chars.by_ref() {
+            if ('\u{40}'..='\u{7e}').contains(&c) {
+                final_byte = Some(c);
+                break;
+            }
+            params.push(c);
+        }
+
+        if final_byte != Some('m') {
+            continue;
+        }
+
+        let new_style = apply_sgr(style, &params);
+        if new_style != style {
+            push_span(&mut spans, span_start..stripped.len(), style);
+            span_start = stripped.len();
+            style = new_style;
+        }
+    }
+    push_span(&mut spans, span_start..stripped.len(), style);
+
+    let has_style = spans.iter().any(|span| span.style != AnsiStyle::default());
+    (stripped, has_style.then_some(spans))
+}
+
+fn push_span(spans: &mut Vec<AnsiSpan>, range: Range<usize>, style: AnsiStyle) {
+    if !range.is_empty() {
+        spans.push(AnsiSpan { range, style });
+    }
+}
+
+fn apply_sgr(mut style: AnsiStyle, params: &str) -> AnsiStyle {
+    // `ESC[m` is the same as `ESC[0m`
+    let codes: Vec<u32> = params
+        .split(';')
+        .map(|code| code.parse::<u32>().unwrap_or(0))
+        .collect();
+
+    let mut codes = codes.into_iter();
+    while let Some(code) = codes.next() {
+        match code {
+            0 => style = AnsiStyle::default(),
+            1 => style.bold = true,
+            3 => style.italic = true,
+            4 => style.underline = true,
+            22 => style.bold = false,
+            23 => style.italic = false,
+            24 => style.underline = false,
+            30..=37 => style.foreground = Some(PALETTE[(code - 30) as usize]),
+            38 => style.foreground = parse_extended_color(&mut codes),
+            39 => style.foreground = None,
+            40..=47 => style.background = Some(PALETTE[(code - 40) as usize]),
+            48 => style.background = parse_extended_color(&mut codes),
+            49 => style.background = None,
+            90..=97 => style.foreground = Some(PALETTE[(code - 90 + 8) as usize]),
+            100..=107 => style.background = Some(PALETTE[(code - 100 + 8) as usize]),
+            _ => {}
+        }
+    }
+
+    style
+}
+
+// `5;n` for the 256 colors palette, `2;r;g;b` for true colors
+fn parse_extended_color(codes: &mut impl Iterator<Item = u32>) -> Option<Color32> {
+    match codes.next()? {
+        5 => Some(color_from_256_palette(codes.next()?)),
+        2 => {
+            let r = codes.next()?.min(255) as u8;
+            let g = codes.next()?.min(255) as u8;
+            let b = codes.next()?.min(255) as u8;
+            Some(Color32::from_rgb(r, g, b))
+        }
+        _ => None,
+    }
+}
+
+fn color_from_256_palette(index: u32) -> Color32 {
+    match index {
+        0..=15 => PALETTE[index as usize],
+        16..=231 => {
+            let index = index - 16;
+            let level = |value: u32| if value == 0 { 0 } else { (55 + value * 40) as u8 };
+            Color32::from_rgb(level(index / 36), level((index / 6) % 6), level(index % 6))
+        }
+        _ => {
+            let gray = (8 + (index.min(255) - 232) * 10) as u8;
+            Color32::from_gray(gray)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(range: Range<usize>, style: AnsiStyle) -> AnsiSpan {
+        AnsiSpan { range, style }
+    }
+
+    fn foreground(color: Color32) -> AnsiStyle {
+        AnsiStyle {
+            foreground: Some(color),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn text_without_escapes_has_no_spans() {
+        assert_eq!(
+            parse("[Info   :   BepInEx] Loading [R2API 5.0.5]"),
+            (
+                "[Info   :   BepInEx] Loading [R2API 5.0.5]".to_string(),
+                None
+            )
+        );
+    }
+
+    #[test]
+    fn reset_ends_the_span() {
+        let (text, spans) = parse("\u{1b}[31mError\u{1b}[0m done");
+
+        assert_eq!(text, "Error done");
+        assert_eq!(
+            spans.unwrap(),
+            vec![
+                span(0..5, foreground(PALETTE[1])),
+                span(5..10, AnsiStyle::default()),
+            ]
+        );
+        assert_eq!(&text[0..5], "Error");
+    }
+
+    #[test]
+    fn empty_sgr_is_a_reset() {
+        let (text, spans) = parse("\u{1b}[1mbold\u{1b}[m plain");
+
+        assert_eq!(text, "bold plain");
+        assert_eq!(spans.unwrap()[1], span(4..10, AnsiStyle::default()));
+    }
+
+    #[test]
+    fn bold_combines_with_the_color() {
+        let (text, spans) = parse("\u{1b}[1;92mOK\u{1b}[22m!");
+
+        assert_eq!(text, "OK!");
+        assert_eq!(
+            spans.unwrap(),
+            vec![
+                span(
+                    0..2,
+                    AnsiStyle {
+                        foreground: Some(PALETTE[10]),
+                        bold: true,
+                        ..Default::default()
+                    }
+                ),
+                span(2..3, foreground(PALETTE[10])),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_256_colors() {
+        let (text, spans) = parse("\u{1b}[38;5;196mred\u{1b}[48;5;244mgray\u{1b}[38;5;4mblue");
+
+        assert_eq!(text, "redgrayblue");
+        let spans = spans.unwrap();
+        assert_eq!(
+            spans[0],
+            span(0..3, foreground(Color32::from_rgb(255, 0, 0)))
+        );
+        assert_eq!(spans[1].range, 3..7);
+        assert_eq!(spans[1].style.background, Some(Color32::from_gray(128)));
+        assert_eq!(spans[2].range, 7..11);
+        assert_eq!(spans[2].style.foreground, Some(PALETTE[4]));
+    }
+
+    #[test]
+    fn parses_true_colors() {
+        let (text, spans) = parse("\u{1b}[38;2;255;128;0morange\u{1b}[39m");
+
+        assert_eq!(text, "orange");
+        assert_eq!(
+            spans.unwrap(),
+            vec![span(0..6, foreground(Color32::from_rgb(255, 128, 0)))]
+        );
+    }
+
+    #[test]
+    fn text_split_by_a_sequence_lines_up() {
+        let (text, spans) = parse("Hél\u{1b}[4mlo wor\u{1b}[24mld");
+        let spans = spans.unwrap();
+
+        assert_eq!(text, "Héllo world");
+        assert_eq!(&text[spans[0].range.clone()], "Hél");
+        assert_eq!(&text[spans[1].range.clone()], "lo wor");
+        assert!(spans[1].style.underline);
+        assert_eq!(&text[spans[2].range.clone()], "ld");
+        assert_eq!(spans[2].style, AnsiStyle::default());
+    }
+
+    #[test]
+    fn other_sequences_are_stripped_without_style() {
+        let (text, spans) = parse("\u{1b}[2Kline\u{1b}[1A\u{1b}");
+
+        assert_eq!(text, "line");
+        assert_eq!(spans, None);
+    }
+}
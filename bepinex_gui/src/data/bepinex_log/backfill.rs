@@ -0,0 +1,267 @@
+use std::{
+    fs,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use super::{parser::LogFileParser, BepInExLogEntry};
+
+// Past that many socket logs without knowing where they start in the file, the first match is taken
+const MAX_PENDING_SOCKET_LOGS: usize = 16;
+// Same when the game goes quiet before the socket logs could be located
+const MAX_PENDING_DURATION: Duration = Duration::from_millis(500);
+
+/// Merges the lines written to the log file before the socket connected (preloader...)
+/// ahead of the socket stream, without duplicating the ones that both have.
+///
+/// The socket stream is located in the file by content and order:
+/// the first socket logs are held back until a single position of the file matches them.
+#[derive(Default)]
+pub struct Backfill {
+    // `None` once merged, or when there was nothing to merge
+    file_logs: Option<Vec<BepInExLogEntry>>,
+    pending_socket_logs: Vec<BepInExLogEntry>,
+    // when the first pending socket log came
+    pending_since: Option<Instant>,
+    // socket logs before that index aren't in the file (e.g. filtered out by the disk log level)
+    match_start: usize,
+}
+
+impl Backfill {
+    /// Reads the log file as it is right now.
+    pub fn read(log_file_full_path: &Path) -> Self {
+        let bytes = match fs::read(log_file_full_path) {
+            Ok(bytes) => bytes,
+            Err(err) => {
+                tracing::error!(
+                    "Failed reading {:?} for backfilling: {}",
+                    log_file_full_path,
+                    err
+                );
+                return Self::default();
+            }
+        };
+
+        let mut parser = LogFileParser::default();
+        let mut file_logs: Vec<BepInExLogEntry> = String::from_utf8_lossy(&bytes)
+            .lines()
+            .filter_map(|line| parser.push_line(line))
+            .collect();
+        file_logs.extend(parser.flush());
+
+        Self {
+            file_logs: Some(file_logs),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the logs to send, in order. Empty while the socket stream is still being located.
+    pub fn merge(&mut self, socket_log: BepInExLogEntry) -> Vec<BepInExLogEntry> {
+        let Some(file_logs) = &self.file_logs else {
+            return vec![socket_log];
+        };
+
+        self.pending_socket_logs.push(socket_log);
+        let pending_since = *self.pending_since.get_or_insert_with(Instant::now);
+
+        // nothing to locate the socket logs in
+        if file_logs.is_empty() {
+            return self.finish(None);
+        }
+
+        let mut candidates = self.find_candidates(file_logs);
+        while candidates.is_empty() && self.match_start < self.pending_socket_logs.len() {
+            self.match_start += 1;
+            candidates = self.find_candidates(file_logs);
+        }
+
+        let is_located = candidates.len() == 1;
+        let is_out_of_patience = self.pending_socket_logs.len() >= MAX_PENDING_SOCKET_LOGS
+            || pending_since.elapsed() >= MAX_PENDING_DURATION;
+        if !is_located && !is_out_of_patience {
+            return vec![];
+        }
+
+        self.finish(candidates.first().copied())
+    }
+
+    /// How long the pending socket logs can still wait for more, `None` when none is pending.
+    pub fn time_left(&self) -> Option<Duration> {
+        let pending_since = self.pending_since?;
+        Some(MAX_PENDING_DURATION.saturating_sub(pending_since.elapsed()))
+    }
+
+    /// Gives up on locating the pending socket logs, once no other log came for a while.
+    pub fn flush(&mut self) -> Vec<BepInExLogEntry> {
+        let Some(file_logs) = &self.file_logs else {
+            return vec![];
+        };
+
+        let candidates = self.find_candidates(file_logs);
+        self.finish(candidates.first().copied())
+    }
+
+    // No start at all: the file stopped before the socket started
+    fn finish(&mut self, socket_logs_start: Option<usize>) -> Vec<BepInExLogEntry> {
+        let mut file_logs = self.file_logs.take().unwrap_or_default();
+        let backfill_end = socket_logs_start.unwrap_or(file_logs.len());
+        file_logs.truncate(backfill_end);
+        tracing::info!("Backfilled {} logs from the log file", file_logs.len());
+
+        self.pending_since = None;
+        file_logs.append(&mut self.pending_socket_logs);
+        file_logs
+    }
+
+    // Positions of the file where the pending socket logs could start
+    fn find_candidates(&self, file_logs: &[BepInExLogEntry]) -> Vec<usize> {
+        let sequence = &self.pending_socket_logs[self.match_start..];
+        if sequence.is_empty() {
+            return vec![];
+        }
+
+        (0..file_logs.len())
+            .filter(|&start| {
+                sequence.iter().enumerate().all(|(i, socket_log)| {
+                    // the file can lag behind the socket
+                    file_logs
+                        .get(start + i)
+                        .is_none_or(|file_log| is_same_text(file_log.data(), socket_log.data()))
+                })
+            })
+            .collect()
+    }
+}
+
+// The file parser splits on line breaks, the socket keeps the `\r\n` of multi-line messages
+fn is_same_text(file_text: &str, socket_text: &str) -> bool {
+    let socket_text = socket_text.trim_end();
+    file_text
+        .trim_end()
+        .chars()
+        .eq(socket_text.chars().filter(|c| *c != '\r'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::bepinex_log::LogLevel;
+
+    fn log(text: &str) -> BepInExLogEntry {
+        BepInExLogEntry::new(LogLevel::Info, text)
+    }
+
+    fn backfill(file_lines: &[&str]) -> Backfill {
+        Backfill {
+            file_logs: Some(file_lines.iter().map(|line| log(line)).collect()),
+            ..Default::default()
+        }
+    }
+
+    fn texts(logs: &[BepInExLogEntry]) -> Vec<&str> {
+        logs.iter().map(BepInExLogEntry::data).collect()
+    }
+
+    #[test]
+    fn empty_file_lets_socket_logs_through() {
+        let mut backfill = backfill(&[]);
+
+        let logs = backfill.merge(log("[Info   :   BepInEx] Chainloader ready"));
+
+        assert_eq!(texts(&logs), ["[Info   :   BepInEx] Chainloader ready"]);
+        assert!(backfill.merge(log("[Info   :   BepInEx] Next")).len() == 1);
+        assert!(backfill.time_left().is_none());
+    }
+
+    #[test]
+    fn prepends_file_logs_before_the_socket() {
+        let mut backfill = backfill(&[
+            "[Info   :   BepInEx] Preloader started",
+            "[Info   :   BepInEx] Chainloader ready",
+            "[Info   :   BepInEx] Loading [A 1.0.0]",
+        ]);
+
+        let logs = backfill.merge(log("[Info   :   BepInEx] Chainloader ready"));
+
+        assert_eq!(
+            texts(&logs),
+            [
+                "[Info   :   BepInEx] Preloader started",
+                "[Info   :   BepInEx] Chainloader ready",
+            ]
+        );
+    }
+
+    #[test]
+    fn file_lagging_behind_the_socket() {
+        // the socket starts at the last line of the file, the rest isn't written yet
+        let mut backfill = backfill(&[
+            "[Info   :   BepInEx] A",
+            "[Info   :   BepInEx] B",
+            "[Info   :   BepInEx] A",
+        ]);
+
+        assert!(backfill.merge(log("[Info   :   BepInEx] A")).is_empty());
+        assert!(backfill.merge(log("[Info   :   BepInEx] B")).is_empty());
+        let logs = backfill.merge(log("[Info   :   BepInEx] C"));
+
+        assert_eq!(
+            texts(&logs),
+            [
+                "[Info   :   BepInEx] A",
+                "[Info   :   BepInEx] B",
+                "[Info   :   BepInEx] A",
+                "[Info   :   BepInEx] B",
+                "[Info   :   BepInEx] C",
+            ]
+        );
+        assert_eq!(
+            texts(&backfill.merge(log("[Info   :   BepInEx] D"))),
+            ["[Info   :   BepInEx] D"]
+        );
+    }
+
+    #[test]
+    fn duplicate_lines_wait_for_a_single_match() {
+        let mut backfill = backfill(&[
+            "[Info   :   BepInEx] Preloader started",
+            "[Warning:       Foo] Something",
+            "[Warning:       Foo] Something",
+            "[Info   :   BepInEx] Chainloader ready",
+        ]);
+
+        // could start at either of the duplicates
+        assert!(backfill
+            .merge(log("[Warning:       Foo] Something"))
+            .is_empty());
+        assert!(backfill.time_left().is_some());
+
+        let logs = backfill.merge(log("[Info   :   BepInEx] Chainloader ready"));
+
+        assert_eq!(
+            texts(&logs),
+            [
+                "[Info   :   BepInEx] Preloader started",
+                "[Warning:       Foo] Something",
+                "[Warning:       Foo] Something",
+                "[Info   :   BepInEx] Chainloader ready",
+            ]
+        );
+    }
+
+    #[test]
+    fn flush_gives_up_on_ambiguous_matches() {
+        let mut backfill = backfill(&[
+            "[Warning:       Foo] Something",
+            "[Warning:       Foo] Something",
+        ]);
+
+        assert!(backfill
+            .merge(log("[Warning:       Foo] Something"))
+            .is_empty());
+        let logs = backfill.flush();
+
+        assert_eq!(texts(&logs), ["[Warning:       Foo] Something"]);
+        assert!(backfill.time_left().is_none());
+    }
+}
@@ -0,0 +1,241 @@
+use std::{collections::HashMap, time::SystemTime};
+
+use crate::data::bepinex_mod::BepInExMod;
+
+use super::{BepInExLogEntry, LogLevel};
+
+// How many stack frames are part of an exception signature
+const SIGNATURE_FRAME_COUNT: usize = 3;
+
+// Log sources that don't tell which mod is at fault
+const GENERIC_SOURCES: [&str; 4] = ["Unity Log", "BepInEx", "Preloader", "HarmonyX"];
+
+// Namespaces of the runtime / game frameworks, skipped when guessing the faulty mod from a stack trace
+const FRAMEWORK_NAMESPACES: [&str; 8] = [
+    "System",
+    "UnityEngine",
+    "Unity",
+    "Mono",
+    "MonoMod",
+    "HarmonyLib",
+    "BepInEx",
+    "DMD",
+];
+
+pub struct ErrorGroup {
+    signature: String,
+    title: String,
+    suspected_mod: String,
+    level: LogLevel,
+    // indices of the entries in the log list, in order
+    occurrences: Vec<usize>,
+    first_timestamp: SystemTime,
+    last_timestamp: SystemTime,
+}
+
+impl ErrorGroup {
+    pub fn signature(&self) -> &str {
+        self.signature.as_ref()
+    }
+
+    /// First line of the first occurrence, without the log header.
+    pub fn title(&self) -> &str {
+        self.title.as_ref()
+    }
+
+    pub fn suspected_mod(&self) -> &str {
+        self.suspected_mod.as_ref()
+    }
+
+    /// Most severe level seen for this group.
+    pub const fn level(&self) -> LogLevel {
+        self.level
+    }
+
+    pub fn count(&self) -> usize {
+        self.occurrences.len()
+    }
+
+    pub fn first_index(&self) -> usize {
+        self.occurrences[0]
+    }
+
+    pub fn last_index(&self) -> usize {
+        self.occurrences[self.occurrences.len() - 1]
+    }
+
+    pub const fn first_timestamp(&self) -> SystemTime {
+        self.first_timestamp
+    }
+
+    pub const fn last_timestamp(&self) -> SystemTime {
+        self.last_timestamp
+    }
+
+    pub fn contains(&self, log_index: usize) -> bool {
+        self.occurrences.binary_search(&log_index).is_ok()
+    }
+}
+
+/// `Error` and `Fatal` entries grouped by normalised signature.
+#[derive(Default)]
+pub struct ErrorGroups {
+    groups: Vec<ErrorGroup>,
+    group_index_by_signature: HashMap<String, usize>,
+}
+
+impl ErrorGroups {
+    pub fn groups(&self) -> &[ErrorGroup] {
+        self.groups.as_ref()
+    }
+
+    pub fn get(&self, group_index: usize) -> Option<&ErrorGroup> {
+        self.groups.get(group_index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.groups.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// Log indices must be given in increasing order.
+    pub fn add(&mut self, log_index: usize, log: &BepInExLogEntry, mods: &[BepInExMod]) {
+        if !is_error_level(log.level()) {
+            return;
+        }
+
+        let signature = make_signature(log.message());
+
+        if let Some(&group_index) = self.group_index_by_signature.get(&signature) {
+            let group = &mut self.groups[group_index];
+            group.occurrences.push(log_index);
+            group.last_timestamp = log.timestamp();
+            if log.level() < group.level {
+                group.level = log.level();
+            }
+            return;
+        }
+
+        self.group_index_by_signature
+            .insert(signature.clone(), self.groups.len());
+        self.groups.push(ErrorGroup {
+            signature,
+            title: log.message().lines().next().unwrap_or_default().to_string(),
+            suspected_mod: find_suspected_mod(log, mods),
+            level: log.level(),
+            occurrences: vec![log_index],
+            first_timestamp: log.timestamp(),
+            last_timestamp: log.timestamp(),
+        });
+    }
+}
+
+pub fn is_error_level(level: LogLevel) -> bool {
+    matches!(level, LogLevel::Fatal | LogLevel::Error)
+}
+
+/// Exception type + top stack frames when the message contains an exception,
+/// otherwise the message with the numbers stripped.
+pub fn make_signature(message: &str) -> String {
+    let mut lines = message.lines();
+    let first_line = lines.next().unwrap_or_default().trim();
+
+    if let Some(exception_type) = find_exception_type(first_line) {
+        let frames: Vec<String> = lines
+            .filter_map(normalize_stack_frame)
+            .take(SIGNATURE_FRAME_COUNT)
+            .collect();
+
+        if frames.is_empty() {
+            return format!("{exception_type}: {}", strip_numbers(first_line));
+        }
+
+        return format!("{exception_type} @ {}", frames.join(" | "));
+    }
+
+    strip_numbers(message.trim())
+}
+
+fn find_exception_type(line: &str) -> Option<&str> {
+    line.split(|c: char| c == ':' || c.is_whitespace())
+        .find(|word| word.ends_with("Exception"))
+}
+
+// Keeps only the method part of a stack frame,
+// both for the mono format (`at Foo.Bar () [0x00000] in <hash>:0`)
+// and the unity format (`Foo.Bar () (at path/to/file.cs:12)`)
+fn normalize_stack_frame(line: &str) -> Option<String> {
+    let line = line.trim();
+    let frame = line.strip_prefix("at ").unwrap_or(line);
+
+    if frame.is_empty() || !frame.contains('(') || frame.starts_with("---") {
+        return None;
+    }
+
+    let end = [" [0x", " (at ", " in <", " <0x"]
+        .iter()
+        .filter_map(|marker| frame.find(marker))
+        .min()
+        .unwrap_or(frame.len());
+
+    Some(frame[..end].trim().to_string())
+}
+
+fn strip_numbers(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut previous_was_digit = false;
+
+    for c in text.chars() {
+        if c.is_ascii_digit() {
+            if !previous_was_digit {
+                stripped.push('#');
+            }
+            previous_was_digit = true;
+        } else {
+            stripped.push(c);
+            previous_was_digit = false;
+        }
+    }
+
+    stripped
+}
+
+fn find_suspected_mod(log: &BepInExLogEntry, mods: &[BepInExMod]) -> String {
+    if !log.source().is_empty() && !GENERIC_SOURCES.contains(&log.source()) {
+        return log.source().to_string();
+    }
+
+    let frames: Vec<String> = log
+        .message()
+        .lines()
+        .skip(1)
+        .filter_map(normalize_stack_frame)
+        .filter(|frame| !frame.starts_with('('))
+        .collect();
+
+    for frame in &frames {
+        let frame_lowercase = frame.to_lowercase().replace(['_', ' '], "");
+        for mod_ in mods.iter().filter(|m| !m.name().is_empty()) {
+            let mod_name = mod_.name().to_lowercase().replace(['_', ' '], "");
+            if frame_lowercase.starts_with(&mod_name) {
+                return mod_.name().to_string();
+            }
+        }
+    }
+
+    for frame in &frames {
+        let root_namespace = frame.split(['.', ':', ' ']).next().unwrap_or_default();
+        if !root_namespace.is_empty()
+            && !FRAMEWORK_NAMESPACES
+                .iter()
+                .any(|namespace| root_namespace.starts_with(namespace))
+        {
+            return root_namespace.to_string();
+        }
+    }
+
+    log.source().to_string()
+}
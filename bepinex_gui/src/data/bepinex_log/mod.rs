@@ -1,14 +1,43 @@
+use std::time::SystemTime;
+
 use eframe::emath::Numeric;
 
 use serde::{Deserialize, Serialize};
 use strum::{Display, EnumCount, EnumIter};
 
+use self::ansi::AnsiSpan;
+
+pub mod ansi;
+pub mod backfill;
+pub mod error_groups;
 pub mod file;
+pub mod parser;
 pub mod receiver;
+pub mod rules;
+pub mod startup_profile;
+pub mod stats;
+pub mod tail;
+
+/// Printed once every plugin got loaded.
+pub const CHAINLOADER_DONE_MESSAGE: &str = "Chainloader startup complete";
+
+/// Printed right before the chainloader starts loading the plugins.
+pub const CHAINLOADER_STARTED_MESSAGE: &str = "Chainloader started";
 
 #[allow(dead_code)]
 #[derive(
-    Debug, Clone, Copy, Display, PartialEq, PartialOrd, EnumCount, EnumIter, Serialize, Deserialize,
+    Debug,
+    Clone,
+    Copy,
+    Display,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    EnumCount,
+    EnumIter,
+    Serialize,
+    Deserialize,
 )]
 #[repr(i32)]
 pub enum LogLevel {
@@ -62,18 +91,28 @@ impl Numeric for LogLevel {
 #[derive(Clone)]
 pub struct BepInExLogEntry {
     level: LogLevel,
+    source: String,
     data: String,
     data_lowercase: String,
-    pub is_selected: bool,
+    ansi_spans: Option<Vec<AnsiSpan>>,
+    timestamp: SystemTime,
+    // the lines of the log file only have the time they got read
+    is_timestamp_logged: bool,
 }
 
 impl BepInExLogEntry {
     pub fn new(level: LogLevel, data: &str) -> Self {
+        // escape sequences are kept out of the text, so that copying and filtering ignore them
+        let (data, ansi_spans) = ansi::parse(data);
+
         Self {
             level,
-            data: data.to_string(),
+            source: parse_source(&data).to_string(),
             data_lowercase: data.to_lowercase(),
-            is_selected: false,
+            data,
+            ansi_spans,
+            timestamp: SystemTime::now(),
+            is_timestamp_logged: false,
         }
     }
 
@@ -81,6 +120,38 @@ impl BepInExLogEntry {
         self.level
     }
 
+    /// Name of the log source, e.g. `BepInEx` or `Unity Log`, taken from the `[Level:Source]` header.
+    pub fn source(&self) -> &str {
+        self.source.as_ref()
+    }
+
+    /// The log text without the `[Level:Source]` header.
+    pub fn message(&self) -> &str {
+        strip_header(&self.data)
+    }
+
+    /// Styled ranges of [`Self::data`], when the original text had ANSI colour codes.
+    pub fn ansi_spans(&self) -> Option<&[AnsiSpan]> {
+        self.ansi_spans.as_deref()
+    }
+
+    /// Keeps when the loader logged the entry, instead of when the GUI received it.
+    pub const fn with_timestamp(mut self, timestamp: SystemTime) -> Self {
+        self.timestamp = timestamp;
+        self.is_timestamp_logged = true;
+        self
+    }
+
+    /// When the loader logged the entry, or when the GUI read it for the entries of the log file.
+    pub const fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+
+    /// Whether [`Self::timestamp`] comes from the loader, and can be used for timing.
+    pub const fn is_timestamp_logged(&self) -> bool {
+        self.is_timestamp_logged
+    }
+
     pub fn data(&self) -> &str {
         self.data.as_ref()
     }
@@ -89,3 +160,24 @@ impl BepInExLogEntry {
         self.data_lowercase.as_ref()
     }
 }
+
+// BepInEx formats entries as `[Level  :     Source] Message`
+fn header_end(data: &str) -> Option<usize> {
+    if !data.starts_with('[') {
+        return None;
+    }
+
+    let end = data.find(']')?;
+    data[..end].contains(':').then_some(end)
+}
+
+fn parse_source(data: &str) -> &str {
+    header_end(data).map_or("", |end| {
+        let header = &data[1..end];
+        header[header.find(':').unwrap() + 1..].trim()
+    })
+}
+
+fn strip_header(data: &str) -> &str {
+    header_end(data).map_or(data, |end| data[end + 1..].trim_start())
+}
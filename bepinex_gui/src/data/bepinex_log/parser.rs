@@ -0,0 +1,60 @@
+use super::{header_end, BepInExLogEntry, LogLevel};
+
+/// Builds entries out of the lines of a `LogOutput.log`.
+///
+/// Lines without a `[Level  :   Source]` header belong to the previous entry
+/// (stack traces, multi-line messages...).
+#[derive(Default)]
+pub struct LogFileParser {
+    pending_entry: Option<(LogLevel, String)>,
+}
+
+impl LogFileParser {
+    /// Returns the previous entry once `line` starts a new one.
+    pub fn push_line(&mut self, line: &str) -> Option<BepInExLogEntry> {
+        let line = line.trim_end_matches(['\r', '\n']);
+
+        match parse_level(line) {
+            Some(level) => {
+                let entry = self.flush();
+                self.pending_entry = Some((level, line.to_string()));
+                entry
+            }
+            None => {
+                match &mut self.pending_entry {
+                    Some((_, text)) => {
+                        text.push('\n');
+                        text.push_str(line);
+                    }
+                    // text from before the first header, shouldn't happen with BepInEx
+                    None => self.pending_entry = Some((LogLevel::Message, line.to_string())),
+                }
+                None
+            }
+        }
+    }
+
+    /// The last entry is only known to be complete once the next one starts, or at the end of the file.
+    pub fn flush(&mut self) -> Option<BepInExLogEntry> {
+        self.pending_entry
+            .take()
+            .map(|(level, text)| BepInExLogEntry::new(level, &text))
+    }
+}
+
+/// Level from the `[Level  :   Source]` header of the line, `None` if it doesn't start with one.
+pub fn parse_level(line: &str) -> Option<LogLevel> {
+    let end = header_end(line)?;
+    let header = &line[1..end];
+    let level_name = header[..header.find(':')?].trim();
+
+    match level_name {
+        "Fatal" => Some(LogLevel::Fatal),
+        "Error" => Some(LogLevel::Error),
+        "Warning" => Some(LogLevel::Warning),
+        "Message" => Some(LogLevel::Message),
+        "Info" => Some(LogLevel::Info),
+        "Debug" => Some(LogLevel::Debug),
+        _ => None,
+    }
+}
@@ -4,38 +4,71 @@ use std::net::Ipv4Addr;
 use std::net::SocketAddr;
 use std::net::TcpStream;
 
+use std::fs;
 use std::io;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
 
 use std::thread;
+use std::time::SystemTime;
 
 use crossbeam_channel::Sender;
 
+use crate::backend::network::commands::CommandChannel;
 use crate::backend::network::packet_protocol;
+use crate::backend::network::packet_protocol::PacketKind;
+use crate::data::bepinex_mod::parser::PluginTracker;
 use crate::data::bepinex_mod::BepInExMod;
 
+use super::backfill::Backfill;
+use super::parser::LogFileParser;
+use super::tail;
 use super::BepInExLogEntry;
 use super::LogLevel;
 
+// Failing that many times in a row without ever connecting means the loader listener is blocked
+// (firewall, antivirus, port conflict...), BepInEx still writes the log file though
+const CONNECTION_ATTEMPTS_BEFORE_FILE_FALLBACK: u32 = 3;
+
 #[derive(Clone)]
 pub struct LogReceiver {
     log_socket_port_receiver: u16,
+    // followed instead of the socket when it never connects
+    log_file_full_path: PathBuf,
     log_senders: Vec<Sender<BepInExLogEntry>>,
     mod_senders: Vec<Sender<BepInExMod>>,
+    plugin_tracker: Arc<Mutex<PluginTracker>>,
+    is_tailing_file: Arc<AtomicBool>,
+    command_channel: CommandChannel,
 }
 
 impl LogReceiver {
     pub fn new(
         log_socket_port_receiver: u16,
+        log_file_full_path: PathBuf,
         log_senders: Vec<Sender<BepInExLogEntry>>,
         mod_senders: Vec<Sender<BepInExMod>>,
+        command_channel: CommandChannel,
     ) -> Self {
         Self {
             log_socket_port_receiver,
+            log_file_full_path,
             log_senders,
             mod_senders,
+            plugin_tracker: Arc::default(),
+            is_tailing_file: Arc::new(AtomicBool::new(false)),
+            command_channel,
         }
     }
 
+    /// Whether the logs come from the log file because the socket never connected.
+    pub fn is_tailing_file(&self) -> bool {
+        self.is_tailing_file.load(Ordering::Relaxed)
+    }
+
     pub fn start_thread_loop(&self) {
         let server_address = SocketAddr::new(
             IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)),
@@ -43,51 +76,47 @@ impl LogReceiver {
         );
         let inst = self.clone();
         thread::spawn(move || -> io::Result<()> {
+            let mut has_connected = false;
+            let mut failed_connection_attempts = 0;
+            let mut backfill = Backfill::default();
+
             loop {
                 match TcpStream::connect(server_address) {
-                    Ok(mut tcp_stream) => loop {
-                        match packet_protocol::read_packet_length(&mut tcp_stream) {
-                            Ok(packet_length) => {
-                                match packet_protocol::read_packet_log_level(&mut tcp_stream) {
-                                    Ok(log_level) => {
-                                        match packet_protocol::read_packet(
-                                            &mut tcp_stream,
-                                            packet_length,
-                                        ) {
-                                            Ok(packet_bytes) => {
-                                                inst.make_log_entry_from_packet_data(
-                                                    log_level,
-                                                    &packet_bytes,
-                                                );
-                                            }
-                                            Err(err) => {
-                                                tracing::error!(
-                                                    "Error reading packet: {}\nDisconnecting socket",
-                                                    err
-                                                );
-                                                break;
-                                            }
-                                        }
-                                    }
-                                    Err(err) => {
-                                        tracing::error!(
-                                            "Error reading packet log level: {}\nDisconnecting socket",
-                                            err
-                                        );
-                                        break;
-                                    }
-                                }
-                            }
-                            Err(err) => {
-                                tracing::error!(
-                                    "Error reading packet length: {}\nDisconnecting socket",
-                                    err
-                                );
-                                break;
+                    Ok(mut tcp_stream) => match packet_protocol::read_handshake(&mut tcp_stream) {
+                        Ok(()) => {
+                            if !has_connected {
+                                // the loader only queues the lines logged after the GUI got launched
+                                backfill = Backfill::read(&inst.log_file_full_path);
+                                has_connected = true;
                             }
+
+                            inst.command_channel.connect(&tcp_stream);
+                            inst.receive_packets_until_disconnected(&mut tcp_stream, &mut backfill);
+                            inst.command_channel.disconnect();
                         }
+                        Err(err) if err.kind() == io::ErrorKind::InvalidData => {
+                            tracing::error!(
+                                "Can't read the packets of the loader, {}. \
+                                 Update the GUI and the loader together",
+                                err
+                            );
+                            inst.tail_log_file();
+                            return Ok(());
+                        }
+                        Err(err) => tracing::error!("Error reading handshake: {}", err),
                     },
-                    Err(err) => tracing::error!("Failed connecting: {}", err),
+                    Err(err) => {
+                        tracing::error!("Failed connecting: {}", err);
+
+                        failed_connection_attempts += 1;
+                        if !has_connected
+                            && failed_connection_attempts
+                                >= CONNECTION_ATTEMPTS_BEFORE_FILE_FALLBACK
+                        {
+                            inst.tail_log_file();
+                            return Ok(());
+                        }
+                    }
                 }
 
                 const DELAY_IN_MS_BETWEEN_CONNECTION_TRY: u64 = 2000;
@@ -98,30 +127,185 @@ impl LogReceiver {
         });
     }
 
-    fn make_log_entry_from_packet_data(&self, log_level: LogLevel, string_packet_bytes: &[u8]) {
-        let log_string = packet_protocol::packet_bytes_to_utf8_string(string_packet_bytes);
+    fn receive_packets_until_disconnected(
+        &self,
+        tcp_stream: &mut TcpStream,
+        backfill: &mut Backfill,
+    ) {
+        loop {
+            if let Some(time_left) = backfill.time_left() {
+                if !wait_for_packet(tcp_stream, time_left) {
+                    for log in backfill.flush() {
+                        self.dispatch(log);
+                    }
+                }
+            }
+
+            let packet_length = match packet_protocol::read_packet_length(tcp_stream) {
+                Ok(packet_length) => packet_length,
+                Err(err) => {
+                    tracing::error!("Error reading packet length: {}\nDisconnecting socket", err);
+                    return;
+                }
+            };
+
+            let packet_kind = match packet_protocol::read_packet_kind(tcp_stream) {
+                Ok(packet_kind) => packet_kind,
+                Err(err) => {
+                    tracing::error!("Error reading packet kind: {}\nDisconnecting socket", err);
+                    return;
+                }
+            };
+
+            let packet_timestamp = match packet_protocol::read_packet_timestamp(tcp_stream) {
+                Ok(packet_timestamp) => packet_timestamp,
+                Err(err) => {
+                    tracing::error!("Error reading packet timestamp: {}\nDisconnecting socket", err);
+                    return;
+                }
+            };
 
-        let log = BepInExLogEntry::new(log_level, &log_string);
-
-        if log.data().contains("Loading [") {
-            let split: Vec<&str> = log.data().split('[').collect();
-            let mod_info_text = split[2];
-            let mod_version_start_index_ = mod_info_text.rfind(' ');
-            if let Some(mod_version_start_index) = mod_version_start_index_ {
-                let mod_name = &mod_info_text[0..mod_version_start_index];
-                let mod_version =
-                    &mod_info_text[mod_version_start_index + 1..mod_info_text.len() - 1];
-
-                for mod_sender in &self.mod_senders {
-                    mod_sender
-                        .send(BepInExMod::new(mod_name, mod_version))
-                        .unwrap();
+            match packet_protocol::read_packet(tcp_stream, packet_length) {
+                Ok(packet_bytes) => match packet_kind {
+                    PacketKind::Log(log_level) => {
+                        self.make_log_entry_from_packet_data(
+                            backfill,
+                            log_level,
+                            packet_timestamp,
+                            &packet_bytes,
+                        );
+                    }
+                    PacketKind::CommandResponse => {
+                        self.make_command_response_entry_from_packet_data(&packet_bytes);
+                    }
+                    PacketKind::CommandList => self.command_channel.set_available_commands(
+                        &packet_protocol::packet_bytes_to_utf8_string(&packet_bytes),
+                    ),
+                    PacketKind::PluginInfoList => {
+                        let changed_mods = self.plugin_tracker.lock().unwrap().push_plugin_info_list(
+                            &packet_protocol::packet_bytes_to_utf8_string(&packet_bytes),
+                        );
+                        self.send_mods(changed_mods);
+                    }
+                },
+                Err(err) => {
+                    tracing::error!("Error reading packet: {}\nDisconnecting socket", err);
+                    return;
                 }
             }
         }
+    }
+
+    /// Reads a whole `LogOutput.log` instead of listening to the loader, for viewing logs offline.
+    pub fn start_thread_read_file(&self, log_file_full_path: PathBuf) {
+        let inst = self.clone();
+        thread::spawn(move || {
+            let bytes = match fs::read(&log_file_full_path) {
+                Ok(bytes) => bytes,
+                Err(err) => {
+                    tracing::error!("Failed reading {:?}: {}", log_file_full_path, err);
+                    return;
+                }
+            };
+
+            let mut parser = LogFileParser::default();
+            for line in String::from_utf8_lossy(&bytes).lines() {
+                if let Some(log) = parser.push_line(line) {
+                    if !inst.dispatch(log) {
+                        // another file got opened in the meantime
+                        return;
+                    }
+                }
+            }
+
+            if let Some(log) = parser.flush() {
+                inst.dispatch(log);
+            }
+        });
+    }
+
+    fn tail_log_file(&self) {
+        tracing::warn!(
+            "Could not connect to the loader, following {:?} instead",
+            self.log_file_full_path
+        );
+
+        self.is_tailing_file.store(true, Ordering::Relaxed);
+        tail::follow(&self.log_file_full_path, |log| self.dispatch(log));
+    }
+
+    fn make_log_entry_from_packet_data(
+        &self,
+        backfill: &mut Backfill,
+        log_level: LogLevel,
+        timestamp: SystemTime,
+        string_packet_bytes: &[u8],
+    ) {
+        let log_string = packet_protocol::packet_bytes_to_utf8_string(string_packet_bytes);
+
+        let log = BepInExLogEntry::new(log_level, &log_string).with_timestamp(timestamp);
+
+        for log in backfill.merge(log) {
+            self.dispatch(log);
+        }
+    }
+
+    // Shown in the console like any other log, under a `Command` source
+    fn make_command_response_entry_from_packet_data(&self, string_packet_bytes: &[u8]) {
+        let response = packet_protocol::packet_bytes_to_utf8_string(string_packet_bytes);
+
+        let log = BepInExLogEntry::new(
+            LogLevel::Message,
+            &format!("[{:<7}:{:>10}] {response}", "Message", "Command"),
+        );
+
+        self.dispatch(log);
+    }
+
+    // Returns false once nobody is listening anymore
+    fn dispatch(&self, log: BepInExLogEntry) -> bool {
+        let changed_mods = self.plugin_tracker.lock().unwrap().push_log(&log);
+        let mut is_listened_to = self.send_mods(changed_mods);
 
         for log_sender in &self.log_senders {
-            log_sender.send(log.clone()).unwrap();
+            is_listened_to &= log_sender.send(log.clone()).is_ok();
         }
+
+        is_listened_to
+    }
+
+    // Returns false once nobody is listening anymore
+    fn send_mods(&self, mods: Vec<BepInExMod>) -> bool {
+        let mut is_listened_to = true;
+
+        for mod_ in mods {
+            for mod_sender in &self.mod_senders {
+                is_listened_to &= mod_sender.send(mod_.clone()).is_ok();
+            }
+        }
+
+        is_listened_to
     }
 }
+
+// Returns false when nothing came in time, without consuming anything from the stream
+fn wait_for_packet(tcp_stream: &TcpStream, timeout: time::Duration) -> bool {
+    // a zero timeout is rejected
+    if tcp_stream
+        .set_read_timeout(Some(timeout.max(time::Duration::from_millis(1))))
+        .is_err()
+    {
+        return true;
+    }
+
+    let has_packet = !matches!(
+        tcp_stream.peek(&mut [0; 1]),
+        Err(err) if matches!(err.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut)
+    );
+
+    if let Err(err) = tcp_stream.set_read_timeout(None) {
+        tracing::error!("Failed resetting the socket read timeout: {}", err);
+    }
+
+    has_packet
+}
@@ -0,0 +1,156 @@
+use eframe::epaint::Color32;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter};
+
+use super::{BepInExLogEntry, LogLevel};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumIter, Serialize, Deserialize)]
+pub enum RuleTarget {
+    Text,
+    Source,
+}
+
+/// User defined rule that changes how the matching log entries are displayed in the console.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LogRule {
+    pub enabled: bool,
+    pub target: RuleTarget,
+    pub pattern: String,
+    // otherwise a case insensitive "contains"
+    pub is_regex: bool,
+    pub color: Option<Color32>,
+    pub bold: bool,
+    pub hide: bool,
+    // effective level of the entry, for filtering purposes
+    pub level: Option<LogLevel>,
+}
+
+impl Default for LogRule {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            target: RuleTarget::Text,
+            pattern: String::new(),
+            is_regex: false,
+            color: None,
+            bold: false,
+            hide: false,
+            level: None,
+        }
+    }
+}
+
+/// What the matching rules do to a log entry. Later rules win over earlier ones.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RuleOutcome {
+    pub color: Option<Color32>,
+    pub bold: bool,
+    pub hide: bool,
+    pub level: Option<LogLevel>,
+}
+
+enum Matcher {
+    Regex(Regex),
+    Contains(String),
+}
+
+impl Matcher {
+    fn is_match(&self, text: &str, text_lowercase: &str) -> bool {
+        match self {
+            Self::Regex(regex) => regex.is_match(text),
+            Self::Contains(pattern_lowercase) => text_lowercase.contains(pattern_lowercase),
+        }
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct LogRules {
+    rules: Vec<LogRule>,
+
+    // one per rule, `Err` when the regex is invalid
+    #[serde(skip)]
+    matchers: Vec<Result<Matcher, String>>,
+
+    // bumped each time the rules change, so that cached outcomes can be invalidated
+    #[serde(skip)]
+    generation: u64,
+}
+
+impl LogRules {
+    pub fn rules(&self) -> &[LogRule] {
+        self.rules.as_ref()
+    }
+
+    /// [`Self::rules_changed`] must be called once done editing.
+    pub fn rules_mut(&mut self) -> &mut Vec<LogRule> {
+        &mut self.rules
+    }
+
+    pub const fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Error message of the rule, if its regex doesn't compile.
+    pub fn error(&self, rule_index: usize) -> Option<&str> {
+        self.matchers
+            .get(rule_index)
+            .and_then(|matcher| matcher.as_ref().err())
+            .map(|err| err.as_ref())
+    }
+
+    pub fn rules_changed(&mut self) {
+        self.matchers = self
+            .rules
+            .iter()
+            .map(|rule| {
+                if rule.is_regex {
+                    RegexBuilder::new(&rule.pattern)
+                        .case_insensitive(true)
+                        .build()
+                        .map(Matcher::Regex)
+                        .map_err(|err| err.to_string())
+                } else {
+                    Ok(Matcher::Contains(rule.pattern.to_lowercase()))
+                }
+            })
+            .collect();
+
+        self.generation += 1;
+    }
+
+    pub fn evaluate(&self, log: &BepInExLogEntry) -> RuleOutcome {
+        let mut outcome = RuleOutcome::default();
+
+        let source_lowercase = log.source().to_lowercase();
+        for (rule, matcher) in self.rules.iter().zip(&self.matchers) {
+            let Ok(matcher) = matcher else {
+                continue;
+            };
+
+            if !rule.enabled || rule.pattern.is_empty() {
+                continue;
+            }
+
+            let is_match = match rule.target {
+                RuleTarget::Text => matcher.is_match(log.data(), log.data_lowercase()),
+                RuleTarget::Source => matcher.is_match(log.source(), &source_lowercase),
+            };
+            if !is_match {
+                continue;
+            }
+
+            if rule.color.is_some() {
+                outcome.color = rule.color;
+            }
+            if rule.level.is_some() {
+                outcome.level = rule.level;
+            }
+            outcome.bold |= rule.bold;
+            outcome.hide |= rule.hide;
+        }
+
+        outcome
+    }
+}
@@ -0,0 +1,108 @@
+use std::time::{Duration, SystemTime};
+
+use crate::data::bepinex_mod::BepInExMod;
+
+use super::{BepInExLogEntry, CHAINLOADER_DONE_MESSAGE, CHAINLOADER_STARTED_MESSAGE};
+
+pub struct PluginLoadStep {
+    mod_: BepInExMod,
+    // until the next plugin started loading, `None` while it's the last one
+    duration: Option<Duration>,
+}
+
+impl PluginLoadStep {
+    pub const fn mod_(&self) -> &BepInExMod {
+        &self.mod_
+    }
+
+    pub fn start(&self) -> SystemTime {
+        // always set for the mods parsed from a loading line
+        self.mod_
+            .loading_timestamp()
+            .unwrap_or(SystemTime::UNIX_EPOCH)
+    }
+
+    pub const fn duration(&self) -> Option<Duration> {
+        self.duration
+    }
+}
+
+/// How long each plugin took to load, from the chainloader `Loading [Name Version]` lines.
+///
+/// Timestamps are the ones of the loader logging the lines. Lines read back from the log file
+/// only have the time they got read, the profile isn't timed once one of them is part of it.
+#[derive(Default)]
+pub struct StartupProfile {
+    chainloader_start: Option<SystemTime>,
+    steps: Vec<PluginLoadStep>,
+    completion: Option<SystemTime>,
+    has_untimed_lines: bool,
+}
+
+impl StartupProfile {
+    pub fn add(&mut self, log: &BepInExLogEntry) {
+        if self.completion.is_some() {
+            return;
+        }
+
+        let is_chainloader_line = log.message() == CHAINLOADER_STARTED_MESSAGE
+            || log.message() == CHAINLOADER_DONE_MESSAGE;
+        let mod_ = BepInExMod::from_loading_log(log);
+        if (is_chainloader_line || mod_.is_some()) && !log.is_timestamp_logged() {
+            self.has_untimed_lines = true;
+        }
+
+        if let Some(mod_) = mod_ {
+            self.end_last_step(log.timestamp());
+            self.steps.push(PluginLoadStep {
+                mod_,
+                duration: None,
+            });
+        } else if log.message() == CHAINLOADER_STARTED_MESSAGE {
+            self.chainloader_start = Some(log.timestamp());
+        } else if log.message() == CHAINLOADER_DONE_MESSAGE {
+            self.end_last_step(log.timestamp());
+            self.completion = Some(log.timestamp());
+        }
+    }
+
+    fn end_last_step(&mut self, time: SystemTime) {
+        if let Some(last_step) = self.steps.last_mut() {
+            last_step.duration = Some(time.duration_since(last_step.start()).unwrap_or_default());
+        }
+    }
+
+    /// Whether the durations are real, `false` when the lines came from the log file.
+    pub const fn is_timed(&self) -> bool {
+        !self.has_untimed_lines
+    }
+
+    pub fn steps(&self) -> &[PluginLoadStep] {
+        self.steps.as_ref()
+    }
+
+    /// Either the `Chainloader started` line or the first plugin load.
+    pub fn start(&self) -> Option<SystemTime> {
+        self.chainloader_start
+            .or_else(|| self.steps.first().map(PluginLoadStep::start))
+    }
+
+    pub const fn completion(&self) -> Option<SystemTime> {
+        self.completion
+    }
+
+    /// Up to `Chainloader startup complete`, or up to now while still loading.
+    pub fn total_duration(&self) -> Option<Duration> {
+        let end = self.completion.unwrap_or_else(SystemTime::now);
+        Some(end.duration_since(self.start()?).unwrap_or_default())
+    }
+
+    /// Indices of the steps, slowest first.
+    pub fn ranking(&self) -> Vec<usize> {
+        let mut step_indices: Vec<usize> = (0..self.steps.len()).collect();
+        step_indices
+            .sort_by_key(|&i| std::cmp::Reverse(self.steps[i].duration.unwrap_or_default()));
+
+        step_indices
+    }
+}
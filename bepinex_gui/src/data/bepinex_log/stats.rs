@@ -0,0 +1,127 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime},
+};
+
+use super::{error_groups::is_error_level, BepInExLogEntry, LogLevel};
+
+#[derive(Default)]
+pub struct SourceStats {
+    count: usize,
+    error_count: usize,
+}
+
+impl SourceStats {
+    pub const fn count(&self) -> usize {
+        self.count
+    }
+
+    pub const fn error_count(&self) -> usize {
+        self.error_count
+    }
+}
+
+/// Counts of the received entries, per level, per source and per second of the session.
+///
+/// Only the entries timed by the loader count per second, see [`BepInExLogEntry::is_timestamp_logged`].
+#[derive(Default)]
+pub struct LogStats {
+    session_start: Option<SystemTime>,
+    total_count: usize,
+    // read back from the log file, left out of the seconds
+    untimed_count: usize,
+    count_per_level: HashMap<LogLevel, usize>,
+    stats_per_source: HashMap<String, SourceStats>,
+    // one bucket per second since the session start
+    lines_per_second: Vec<u32>,
+    errors_per_second: Vec<u32>,
+    first_error_timestamp: Option<SystemTime>,
+}
+
+impl LogStats {
+    pub fn add(&mut self, log: &BepInExLogEntry) {
+        let is_error = is_error_level(log.level());
+
+        self.total_count += 1;
+        *self.count_per_level.entry(log.level()).or_default() += 1;
+
+        let source_stats = self
+            .stats_per_source
+            .entry(log.source().to_string())
+            .or_default();
+        source_stats.count += 1;
+        if is_error {
+            source_stats.error_count += 1;
+        }
+
+        if !log.is_timestamp_logged() {
+            self.untimed_count += 1;
+            return;
+        }
+
+        let session_start = *self.session_start.get_or_insert(log.timestamp());
+        let second = log
+            .timestamp()
+            .duration_since(session_start)
+            .unwrap_or(Duration::ZERO)
+            .as_secs() as usize;
+        if self.lines_per_second.len() <= second {
+            self.lines_per_second.resize(second + 1, 0);
+            self.errors_per_second.resize(second + 1, 0);
+        }
+        self.lines_per_second[second] += 1;
+
+        if is_error {
+            self.errors_per_second[second] += 1;
+            self.first_error_timestamp.get_or_insert(log.timestamp());
+        }
+    }
+
+    pub const fn session_start(&self) -> Option<SystemTime> {
+        self.session_start
+    }
+
+    pub const fn total_count(&self) -> usize {
+        self.total_count
+    }
+
+    pub const fn untimed_count(&self) -> usize {
+        self.untimed_count
+    }
+
+    pub fn count(&self, level: LogLevel) -> usize {
+        self.count_per_level.get(&level).copied().unwrap_or(0)
+    }
+
+    pub fn source_count(&self) -> usize {
+        self.stats_per_source.len()
+    }
+
+    /// The `count` sources that logged the most, most talkative first.
+    pub fn top_talkers(&self, count: usize) -> Vec<(&str, &SourceStats)> {
+        let mut sources: Vec<(&str, &SourceStats)> = self
+            .stats_per_source
+            .iter()
+            .map(|(source, stats)| (source.as_str(), stats))
+            .collect();
+
+        sources.sort_by(|(a_source, a), (b_source, b)| {
+            b.count.cmp(&a.count).then(a_source.cmp(b_source))
+        });
+        sources.truncate(count);
+
+        sources
+    }
+
+    pub fn lines_per_second(&self) -> &[u32] {
+        self.lines_per_second.as_ref()
+    }
+
+    pub fn errors_per_second(&self) -> &[u32] {
+        self.errors_per_second.as_ref()
+    }
+
+    pub const fn first_error_timestamp(&self) -> Option<SystemTime> {
+        self.first_error_timestamp
+    }
+}
@@ -0,0 +1,104 @@
+use std::{
+    fs::{self, File},
+    io::{Read, Seek, SeekFrom},
+    path::Path,
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+use super::{parser::LogFileParser, BepInExLogEntry};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+// Only the next header tells that an entry is complete,
+// so the last one gets sent once nothing was written for that long
+const FLUSH_DELAY: Duration = Duration::from_millis(500);
+
+#[derive(Default)]
+struct TailState {
+    position: u64,
+    // identifies the file, a new one is created on each game launch
+    creation_time: Option<SystemTime>,
+    // bytes after the last line break
+    partial_line: Vec<u8>,
+    parser: LogFileParser,
+}
+
+/// Follows the log file like `tail -f`, from its first line.
+///
+/// Starts over when the file gets truncated or replaced. Returns once `dispatch` returns false.
+pub fn follow(log_file_full_path: &Path, mut dispatch: impl FnMut(BepInExLogEntry) -> bool) {
+    let mut state = TailState::default();
+    let mut last_read_time = Instant::now();
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+
+        // not created yet, or being replaced
+        let Ok(metadata) = fs::metadata(log_file_full_path) else {
+            continue;
+        };
+
+        let creation_time = metadata.created().ok();
+        let was_replaced = state.creation_time.is_some() && creation_time != state.creation_time;
+        if metadata.len() < state.position || was_replaced {
+            tracing::info!(
+                "{:?} got truncated or replaced, reading it again",
+                log_file_full_path
+            );
+
+            if let Some(log) = state.parser.flush() {
+                if !dispatch(log) {
+                    return;
+                }
+            }
+            state = TailState::default();
+        }
+        state.creation_time = creation_time;
+
+        if metadata.len() == state.position {
+            if last_read_time.elapsed() >= FLUSH_DELAY {
+                if let Some(log) = state.parser.flush() {
+                    if !dispatch(log) {
+                        return;
+                    }
+                }
+            }
+            continue;
+        }
+
+        match read_from(log_file_full_path, state.position) {
+            Ok(bytes) => {
+                state.position += bytes.len() as u64;
+                last_read_time = Instant::now();
+
+                state.partial_line.extend_from_slice(&bytes);
+                let Some(last_line_break) = state.partial_line.iter().rposition(|b| *b == b'\n')
+                else {
+                    continue;
+                };
+                let complete_lines: Vec<u8> =
+                    state.partial_line.drain(..=last_line_break).collect();
+
+                for line in String::from_utf8_lossy(&complete_lines).lines() {
+                    if let Some(log) = state.parser.push_line(line) {
+                        if !dispatch(log) {
+                            return;
+                        }
+                    }
+                }
+            }
+            Err(err) => tracing::error!("Failed reading {:?}: {}", log_file_full_path, err),
+        }
+    }
+}
+
+fn read_from(log_file_full_path: &Path, position: u64) -> std::io::Result<Vec<u8>> {
+    let mut file = File::open(log_file_full_path)?;
+    file.seek(SeekFrom::Start(position))?;
+
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+
+    Ok(bytes)
+}
@@ -0,0 +1,256 @@
+use std::{fmt::Display, path::PathBuf, time::SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+use super::bepinex_log::BepInExLogEntry;
+
+pub mod parser;
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LoadStatus {
+    #[default]
+    Loading,
+    Loaded,
+    Skipped(SkipReason),
+    Failed(FailureReason),
+}
+
+impl LoadStatus {
+    pub const fn icon(&self) -> &'static str {
+        match self {
+            Self::Loading => "⌛",
+            Self::Loaded => "✔",
+            Self::Skipped(_) => "⏩",
+            Self::Failed(_) => "❌",
+        }
+    }
+
+    pub const fn is_failed(&self) -> bool {
+        matches!(self, Self::Failed(_))
+    }
+}
+
+impl Display for LoadStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Loading => "Loading",
+            Self::Loaded => "Loaded",
+            Self::Skipped(_) => "Skipped",
+            Self::Failed(_) => "Failed",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SkipReason {
+    // the version that got loaded instead
+    NewerVersionExists(String),
+    // the processes the plugin is limited to
+    ProcessFilter(String),
+    Other(String),
+}
+
+impl SkipReason {
+    /// What happened, for people who don't read BepInEx logs.
+    pub fn explanation(&self) -> String {
+        match self {
+            Self::NewerVersionExists(newer_version) => format!(
+                "Another version of this mod is installed, {newer_version} got loaded instead. \
+                 You can remove this one."
+            ),
+            Self::ProcessFilter(processes) => {
+                format!("This mod only runs in {processes}, not in this game executable.")
+            }
+            Self::Other(reason) => format!("BepInEx skipped this mod because {reason}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FailureReason {
+    // GUIDs, with the minimum version when there is one
+    MissingDependencies(Vec<String>),
+    // one of its dependencies failed or got skipped
+    DependencyNotLoaded,
+    // GUIDs of the installed plugins it can't run with
+    Incompatible(Vec<String>),
+    // message of the exception thrown while loading it
+    Exception(String),
+    Other(String),
+}
+
+impl FailureReason {
+    /// What happened and how to fix it, for people who don't read BepInEx logs.
+    pub fn explanation(&self) -> String {
+        match self {
+            Self::MissingDependencies(dependencies) => format!(
+                "This mod needs other mods that are not installed: {}. \
+                 Install them, or reinstall this mod with your mod manager.",
+                dependencies.join(", ")
+            ),
+            Self::DependencyNotLoaded => {
+                "A mod this one needs failed to load, fix that one first.".to_string()
+            }
+            Self::Incompatible(plugins) => format!(
+                "This mod doesn't work together with {}. Remove one of them.",
+                plugins.join(", ")
+            ),
+            Self::Exception(message) => format!(
+                "This mod crashed while loading, it may be outdated for this version of the game: {message}"
+            ),
+            Self::Other(reason) => format!("BepInEx could not load this mod because {reason}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginDependency {
+    pub guid: String,
+    // soft dependencies only change the load order
+    pub is_soft: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BepInExMod {
+    // GUID, DLL and dependencies come from the loader once the chainloader is done,
+    // only for the plugins that got loaded
+    guid: Option<String>,
+    name: String,
+    version: String,
+    source_dll: Option<PathBuf>,
+    dependencies: Vec<PluginDependency>,
+    // position among the plugins the chainloader started loading
+    load_order_index: Option<usize>,
+    status: LoadStatus,
+    // `Plugin [..] targets a wrong version of BepInEx (5.3)`
+    targeted_bepinex_version: Option<String>,
+    // when the chainloader started loading it
+    loading_timestamp: Option<SystemTime>,
+}
+
+impl BepInExMod {
+    pub fn new(name: impl Into<String>, version: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            version: version.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Mod from the `Loading [Name Version]` line the chainloader prints for each plugin.
+    pub fn from_loading_log(log: &BepInExLogEntry) -> Option<Self> {
+        match parser::parse_plugin_line(log.message())? {
+            parser::PluginLine::Loading { name, version } => {
+                let mut mod_ = Self::new(name, version);
+                mod_.loading_timestamp = Some(log.timestamp());
+                Some(mod_)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn guid(&self) -> Option<&str> {
+        self.guid.as_deref()
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_ref()
+    }
+
+    pub fn version(&self) -> &str {
+        self.version.as_ref()
+    }
+
+    pub fn source_dll(&self) -> Option<&PathBuf> {
+        self.source_dll.as_ref()
+    }
+
+    pub fn dependencies(&self) -> &[PluginDependency] {
+        self.dependencies.as_ref()
+    }
+
+    pub const fn load_order_index(&self) -> Option<usize> {
+        self.load_order_index
+    }
+
+    pub const fn status(&self) -> &LoadStatus {
+        &self.status
+    }
+
+    pub fn targeted_bepinex_version(&self) -> Option<&str> {
+        self.targeted_bepinex_version.as_deref()
+    }
+
+    pub const fn loading_timestamp(&self) -> Option<SystemTime> {
+        self.loading_timestamp
+    }
+
+    /// What the chainloader told about it, for hovering the mod.
+    pub fn details(&self) -> String {
+        let mut details = format!("Status: {}", self.status());
+
+        if let Some(guid) = self.guid() {
+            details += &format!("\nGUID: {guid}");
+        }
+        if let Some(load_order_index) = self.load_order_index() {
+            details += &format!("\nLoad order: {}", load_order_index + 1);
+        }
+        if let Some(source_dll) = self.source_dll() {
+            details += &format!("\nDLL: {}", source_dll.display());
+        }
+        if !self.dependencies().is_empty() {
+            let dependencies: Vec<String> = self
+                .dependencies()
+                .iter()
+                .map(|dependency| match dependency.is_soft {
+                    true => format!("{} (optional)", dependency.guid),
+                    false => dependency.guid.clone(),
+                })
+                .collect();
+            details += &format!("\nDependencies: {}", dependencies.join(", "));
+        }
+        if let Some(targeted_bepinex_version) = self.targeted_bepinex_version() {
+            details += &format!("\nMade for BepInEx {targeted_bepinex_version}");
+        }
+
+        details
+    }
+
+    /// Why it didn't load, or what may go wrong with it, `None` when all is fine.
+    pub fn explanation(&self) -> Option<String> {
+        match &self.status {
+            LoadStatus::Skipped(reason) => Some(reason.explanation()),
+            LoadStatus::Failed(reason) => Some(reason.explanation()),
+            LoadStatus::Loading | LoadStatus::Loaded => {
+                self.targeted_bepinex_version
+                    .as_ref()
+                    .map(|targeted_bepinex_version| {
+                        format!(
+                            "This mod was made for BepInEx {targeted_bepinex_version} \
+                         and might not work until it gets updated."
+                        )
+                    })
+            }
+        }
+    }
+
+    /// Both describe the same plugin, BepInEx names them `Name Version` in its logs.
+    pub fn is_same_plugin(&self, other: &Self) -> bool {
+        self.name == other.name && self.version == other.version
+    }
+}
+
+/// Replaces the previous state of the mod, or adds it at the end.
+pub fn upsert(mods: &mut Vec<BepInExMod>, mod_: BepInExMod) {
+    match mods.iter_mut().find(|m| m.is_same_plugin(&mod_)) {
+        Some(existing_mod) => *existing_mod = mod_,
+        None => mods.push(mod_),
+    }
+}
+
+impl Display for BepInExMod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{} {}", self.name, self.version))
+    }
+}
@@ -0,0 +1,534 @@
+use std::path::PathBuf;
+
+use crate::data::bepinex_log::{BepInExLogEntry, CHAINLOADER_DONE_MESSAGE};
+
+use super::{BepInExMod, FailureReason, LoadStatus, PluginDependency, SkipReason};
+
+/// A chainloader line about a plugin, named `Name Version` by BepInEx.
+#[derive(Debug, PartialEq, Eq)]
+pub enum PluginLine<'a> {
+    Loading {
+        name: &'a str,
+        version: &'a str,
+    },
+    Skipped {
+        name: &'a str,
+        version: &'a str,
+        reason: SkipReason,
+    },
+    Failed {
+        name: &'a str,
+        version: &'a str,
+        reason: FailureReason,
+    },
+    // only a warning, the plugin still gets loaded
+    TargetsWrongBepInEx {
+        name: &'a str,
+        version: &'a str,
+        targeted_version: &'a str,
+    },
+}
+
+/// Recognises the chainloader lines of BepInEx 5 and 6 from the message of a log, without its header.
+pub fn parse_plugin_line(message: &str) -> Option<PluginLine<'_>> {
+    // exceptions come with their stack trace on the next lines
+    let line = message.lines().next()?.trim_end();
+
+    if let Some(plugin) = line
+        .strip_prefix("Loading [")
+        .and_then(|rest| rest.strip_suffix(']'))
+    {
+        let (name, version) = split_name_and_version(plugin);
+        return Some(PluginLine::Loading { name, version });
+    }
+
+    if let Some((plugin, reason)) = split_plugin_and_rest(line, "Skipping [", "] because ") {
+        let (name, version) = split_name_and_version(plugin);
+
+        if reason.starts_with("it has a dependency that was not loaded") {
+            return Some(PluginLine::Failed {
+                name,
+                version,
+                reason: FailureReason::DependencyNotLoaded,
+            });
+        }
+
+        let reason = if let Some(newer_version) = between(reason, "a newer version exists (", ")") {
+            SkipReason::NewerVersionExists(newer_version.to_string())
+        } else if let Some(processes) = between(reason, "of process filters (", ")") {
+            SkipReason::ProcessFilter(processes.to_string())
+        } else {
+            SkipReason::Other(reason.to_string())
+        };
+        return Some(PluginLine::Skipped {
+            name,
+            version,
+            reason,
+        });
+    }
+
+    if let Some((plugin, reason)) = split_plugin_and_rest(line, "Could not load [", "] because ") {
+        let (name, version) = split_name_and_version(plugin);
+
+        let reason =
+            if let Some(dependencies) = reason.strip_prefix("it has missing dependencies: ") {
+                FailureReason::MissingDependencies(split_list(dependencies))
+            } else if let Some(plugins) = reason.strip_prefix("it is incompatible with: ") {
+                FailureReason::Incompatible(split_list(plugins))
+            } else if reason.contains("not loaded") {
+                FailureReason::DependencyNotLoaded
+            } else {
+                FailureReason::Other(reason.to_string())
+            };
+        return Some(PluginLine::Failed {
+            name,
+            version,
+            reason,
+        });
+    }
+
+    if let Some((plugin, exception_message)) = split_plugin_and_rest(line, "Error loading [", "]") {
+        let (name, version) = split_name_and_version(plugin);
+        let exception_message = exception_message.trim_start_matches([' ', ':']);
+        return Some(PluginLine::Failed {
+            name,
+            version,
+            reason: FailureReason::Exception(exception_message.to_string()),
+        });
+    }
+
+    if let Some((plugin, rest)) =
+        split_plugin_and_rest(line, "Plugin [", "] targets a wrong version of BepInEx (")
+    {
+        let (name, version) = split_name_and_version(plugin);
+        return Some(PluginLine::TargetsWrongBepInEx {
+            name,
+            version,
+            targeted_version: rest.split(')').next().unwrap_or_default(),
+        });
+    }
+
+    None
+}
+
+// `Name With Spaces 1.2.3`, the version never has any
+fn split_name_and_version(plugin: &str) -> (&str, &str) {
+    plugin.rsplit_once(' ').unwrap_or((plugin, ""))
+}
+
+// `{prefix}{plugin}{separator}{rest}`
+fn split_plugin_and_rest<'a>(
+    line: &'a str,
+    prefix: &str,
+    separator: &str,
+) -> Option<(&'a str, &'a str)> {
+    let rest = line.strip_prefix(prefix)?;
+    let separator_index = rest.find(separator)?;
+
+    Some((
+        &rest[..separator_index],
+        &rest[separator_index + separator.len()..],
+    ))
+}
+
+fn between<'a>(text: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    text.strip_prefix(start)?.split(end).next()
+}
+
+fn split_list(list: &str) -> Vec<String> {
+    list.split(", ")
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Follows the chainloader through the logs to know the state of each plugin.
+#[derive(Default)]
+pub struct PluginTracker {
+    mods: Vec<BepInExMod>,
+    // the one in `mods` the chainloader is loading, it's loaded once the next one starts
+    loading_mod_index: Option<usize>,
+    loading_count: usize,
+}
+
+impl PluginTracker {
+    /// Mods whose state changed because of this log.
+    pub fn push_log(&mut self, log: &BepInExLogEntry) -> Vec<BepInExMod> {
+        if log.message() == CHAINLOADER_DONE_MESSAGE {
+            return self.finish_loading_mod().into_iter().collect();
+        }
+
+        let Some(plugin_line) = parse_plugin_line(log.message()) else {
+            return vec![];
+        };
+
+        let mut changed_mods: Vec<BepInExMod> = vec![];
+        match plugin_line {
+            PluginLine::Loading { name, version } => {
+                changed_mods.extend(self.finish_loading_mod());
+
+                let mod_index = self.find_or_add(name, version);
+                let mod_ = &mut self.mods[mod_index];
+                mod_.status = LoadStatus::Loading;
+                mod_.load_order_index = Some(self.loading_count);
+                mod_.loading_timestamp = Some(log.timestamp());
+
+                self.loading_count += 1;
+                self.loading_mod_index = Some(mod_index);
+                changed_mods.push(mod_.clone());
+            }
+            PluginLine::Skipped {
+                name,
+                version,
+                reason,
+            } => changed_mods.push(self.set_status(name, version, LoadStatus::Skipped(reason))),
+            PluginLine::Failed {
+                name,
+                version,
+                reason,
+            } => changed_mods.push(self.set_status(name, version, LoadStatus::Failed(reason))),
+            PluginLine::TargetsWrongBepInEx {
+                name,
+                version,
+                targeted_version,
+            } => {
+                let mod_index = self.find_or_add(name, version);
+                self.mods[mod_index].targeted_bepinex_version = Some(targeted_version.to_string());
+                changed_mods.push(self.mods[mod_index].clone());
+            }
+        }
+
+        changed_mods
+    }
+
+    /// From the text of the plugin info packet the loader sends once the chainloader is done,
+    /// one `guid\tname\tversion\tdll path\tdependencies` per loaded plugin.
+    /// Dependencies are comma separated GUIDs, soft ones ending with `?`.
+    pub fn push_plugin_info_list(&mut self, plugin_info_list_text: &str) -> Vec<BepInExMod> {
+        let mut changed_mods = vec![];
+
+        for line in plugin_info_list_text.lines() {
+            let fields: Vec<&str> = line.split('\t').collect();
+            let [guid, name, version, source_dll, dependencies] = fields[..] else {
+                tracing::error!("Unexpected plugin info: {:?}", line);
+                continue;
+            };
+
+            let mod_index = self.find_or_add(name, version);
+            let mod_ = &mut self.mods[mod_index];
+            mod_.guid = Some(guid.to_string());
+            mod_.source_dll = (!source_dll.is_empty()).then(|| PathBuf::from(source_dll));
+            mod_.dependencies = dependencies
+                .split(',')
+                .filter(|dependency| !dependency.is_empty())
+                .map(|dependency| PluginDependency {
+                    guid: dependency.trim_end_matches('?').to_string(),
+                    is_soft: dependency.ends_with('?'),
+                })
+                .collect();
+            // the chainloader only lists the plugins it loaded
+            if mod_.status == LoadStatus::Loading {
+                mod_.status = LoadStatus::Loaded;
+            }
+
+            changed_mods.push(mod_.clone());
+        }
+
+        changed_mods
+    }
+
+    fn find_or_add(&mut self, name: &str, version: &str) -> usize {
+        let mod_ = BepInExMod::new(name, version);
+
+        match self.mods.iter().position(|m| m.is_same_plugin(&mod_)) {
+            Some(mod_index) => mod_index,
+            None => {
+                self.mods.push(mod_);
+                self.mods.len() - 1
+            }
+        }
+    }
+
+    fn set_status(&mut self, name: &str, version: &str, status: LoadStatus) -> BepInExMod {
+        let mod_index = self.find_or_add(name, version);
+        if self.loading_mod_index == Some(mod_index) {
+            self.loading_mod_index = None;
+        }
+
+        self.mods[mod_index].status = status;
+        self.mods[mod_index].clone()
+    }
+
+    fn finish_loading_mod(&mut self) -> Option<BepInExMod> {
+        let mod_ = &mut self.mods[self.loading_mod_index.take()?];
+        mod_.status = LoadStatus::Loaded;
+
+        Some(mod_.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::bepinex_log::{parser::LogFileParser, LogLevel};
+
+    // Excerpt of a Risk of Rain 2 LogOutput.log with BepInEx 5.4.21
+    const ROR2_LOG: &str = "\
+[Message:   BepInEx] BepInEx 5.4.21.0 - Risk of Rain 2 (10/14/2023 8:12:45 PM)
+[Info   :   BepInEx] Running under Unity v2019.4.26.8224325
+[Message:   BepInEx] Preloader started
+[Info   :   BepInEx] Loaded 1 patcher method from [BepInEx.Preloader 5.4.21.0]
+[Message:   BepInEx] Preloader finished
+[Message:   BepInEx] Chainloader ready
+[Message:   BepInEx] Chainloader started
+[Info   :   BepInEx] 7 plugins to load
+[Warning:   BepInEx] Skipping [ItemStats 2.1.0] because a newer version exists (ItemStats 2.2.0)
+[Error  :   BepInEx] Could not load [FasterPickups 1.2.0] because it has missing dependencies: com.bepis.r2api.items (v1.0.0 or newer), com.rune580.riskofoptions
+[Error  :   BepInEx] Could not load [LegacyHud 0.3.1] because it is incompatible with: com.Moffein.ModernHud
+[Warning:   BepInEx] Skipping [PickupTweaks 1.0.0] because it has a dependency that was not loaded. See previous errors for details.
+[Info   :   BepInEx] Loading [R2API 5.0.5]
+[Info   :       R2API] R2API 5.0.5 loaded
+[Warning:   BepInEx] Plugin [OldSkins 1.0.0] targets a wrong version of BepInEx (5.3.0) and might not work until you update
+[Info   :   BepInEx] Loading [OldSkins 1.0.0]
+[Info   :   BepInEx] Loading [Broken Mod 1.0.0]
+[Error  :   BepInEx] Error loading [Broken Mod 1.0.0] : Could not load file or assembly 'MMHOOK_RoR2, Version=0.0.0.0' or one of its dependencies.
+  at BepInEx.Bootstrap.Chainloader.Start () [0x00b5e] in <4589d3ee7ad04fc38d4e9e9c4e9e4b8b>:0
+[Info   :   BepInEx] Loading [Risk Of Options 2.7.1]
+[Message:   BepInEx] Chainloader startup complete
+";
+
+    fn parse_log(text: &str) -> Vec<BepInExLogEntry> {
+        let mut parser = LogFileParser::default();
+        let mut logs: Vec<BepInExLogEntry> = text
+            .lines()
+            .filter_map(|line| parser.push_line(line))
+            .collect();
+        logs.extend(parser.flush());
+        logs
+    }
+
+    fn track(text: &str) -> PluginTracker {
+        let mut tracker = PluginTracker::default();
+        for log in parse_log(text) {
+            tracker.push_log(&log);
+        }
+        tracker
+    }
+
+    fn find<'a>(tracker: &'a PluginTracker, name: &str) -> &'a BepInExMod {
+        tracker
+            .mods
+            .iter()
+            .find(|mod_| mod_.name() == name)
+            .unwrap_or_else(|| panic!("{name} not tracked"))
+    }
+
+    #[test]
+    fn parses_loading_line() {
+        assert_eq!(
+            parse_plugin_line("Loading [Risk Of Options 2.7.1]"),
+            Some(PluginLine::Loading {
+                name: "Risk Of Options",
+                version: "2.7.1"
+            })
+        );
+    }
+
+    #[test]
+    fn ignores_unrelated_lines() {
+        for message in [
+            "Loaded 1 patcher method from [BepInEx.Preloader 5.4.21.0]",
+            "7 plugins to load",
+            "Loading [",
+            "Loading [R2API",
+            "Could not load [Foo 1.0]",
+            "",
+        ] {
+            assert_eq!(parse_plugin_line(message), None, "{message:?}");
+        }
+    }
+
+    #[test]
+    fn parses_missing_dependencies() {
+        assert_eq!(
+            parse_plugin_line(
+                "Could not load [FasterPickups 1.2.0] because it has missing dependencies: \
+                 com.bepis.r2api.items (v1.0.0 or newer), com.rune580.riskofoptions"
+            ),
+            Some(PluginLine::Failed {
+                name: "FasterPickups",
+                version: "1.2.0",
+                reason: FailureReason::MissingDependencies(vec![
+                    "com.bepis.r2api.items (v1.0.0 or newer)".to_string(),
+                    "com.rune580.riskofoptions".to_string(),
+                ]),
+            })
+        );
+    }
+
+    #[test]
+    fn parses_skipped_newer_version() {
+        assert_eq!(
+            parse_plugin_line(
+                "Skipping [ItemStats 2.1.0] because a newer version exists (ItemStats 2.2.0)"
+            ),
+            Some(PluginLine::Skipped {
+                name: "ItemStats",
+                version: "2.1.0",
+                reason: SkipReason::NewerVersionExists("ItemStats 2.2.0".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn parses_process_filter() {
+        assert_eq!(
+            parse_plugin_line(
+                "Skipping [ServerOnly 1.0.0] because of process filters (RoR2Server.exe)"
+            ),
+            Some(PluginLine::Skipped {
+                name: "ServerOnly",
+                version: "1.0.0",
+                reason: SkipReason::ProcessFilter("RoR2Server.exe".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn parses_error_loading_with_stack_trace() {
+        let message =
+            "Error loading [Broken Mod 1.0.0] : Could not load file or assembly 'MMHOOK_RoR2'\n  \
+                       at BepInEx.Bootstrap.Chainloader.Start () [0x00b5e] in <4589d3ee>:0";
+
+        assert_eq!(
+            parse_plugin_line(message),
+            Some(PluginLine::Failed {
+                name: "Broken Mod",
+                version: "1.0.0",
+                reason: FailureReason::Exception(
+                    "Could not load file or assembly 'MMHOOK_RoR2'".to_string()
+                ),
+            })
+        );
+    }
+
+    #[test]
+    fn tracks_statuses_of_a_real_log() {
+        let tracker = track(ROR2_LOG);
+
+        assert_eq!(tracker.mods.len(), 8);
+        assert_eq!(
+            find(&tracker, "ItemStats").status(),
+            &LoadStatus::Skipped(SkipReason::NewerVersionExists(
+                "ItemStats 2.2.0".to_string()
+            ))
+        );
+        assert!(matches!(
+            find(&tracker, "FasterPickups").status(),
+            LoadStatus::Failed(FailureReason::MissingDependencies(dependencies)) if dependencies.len() == 2
+        ));
+        assert_eq!(
+            find(&tracker, "LegacyHud").status(),
+            &LoadStatus::Failed(FailureReason::Incompatible(vec![
+                "com.Moffein.ModernHud".to_string()
+            ]))
+        );
+        assert_eq!(
+            find(&tracker, "PickupTweaks").status(),
+            &LoadStatus::Failed(FailureReason::DependencyNotLoaded)
+        );
+        assert!(matches!(
+            find(&tracker, "Broken Mod").status(),
+            LoadStatus::Failed(FailureReason::Exception(_))
+        ));
+
+        for name in ["R2API", "OldSkins", "Risk Of Options"] {
+            assert_eq!(find(&tracker, name).status(), &LoadStatus::Loaded, "{name}");
+        }
+        assert_eq!(
+            find(&tracker, "OldSkins").targeted_bepinex_version(),
+            Some("5.3.0")
+        );
+    }
+
+    #[test]
+    fn load_order_follows_loading_lines() {
+        let tracker = track(ROR2_LOG);
+
+        let load_order: Vec<(&str, Option<usize>)> = tracker
+            .mods
+            .iter()
+            .filter(|mod_| mod_.load_order_index().is_some())
+            .map(|mod_| (mod_.name(), mod_.load_order_index()))
+            .collect();
+        assert_eq!(
+            load_order,
+            vec![
+                ("R2API", Some(0)),
+                ("OldSkins", Some(1)),
+                ("Broken Mod", Some(2)),
+                ("Risk Of Options", Some(3)),
+            ]
+        );
+        assert_eq!(find(&tracker, "ItemStats").load_order_index(), None);
+    }
+
+    #[test]
+    fn last_plugin_stays_loading_until_chainloader_is_done() {
+        let unfinished_log =
+            ROR2_LOG.replace("[Message:   BepInEx] Chainloader startup complete\n", "");
+        let tracker = track(&unfinished_log);
+
+        assert_eq!(
+            find(&tracker, "Risk Of Options").status(),
+            &LoadStatus::Loading
+        );
+        assert_eq!(find(&tracker, "R2API").status(), &LoadStatus::Loaded);
+    }
+
+    #[test]
+    fn plugin_info_list_fills_guid_dll_and_dependencies() {
+        let mut tracker = track(ROR2_LOG);
+
+        let changed_mods = tracker.push_plugin_info_list(
+            "com.bepis.r2api\tR2API\t5.0.5\tC:\\Game\\BepInEx\\plugins\\R2API\\R2API.dll\t\n\
+             com.rune580.riskofoptions\tRisk Of Options\t2.7.1\tC:\\Game\\BepInEx\\plugins\\RiskOfOptions.dll\tcom.bepis.r2api,com.xoxfaby.BetterUI?\n\
+             malformed line",
+        );
+
+        assert_eq!(changed_mods.len(), 2);
+        let risk_of_options = find(&tracker, "Risk Of Options");
+        assert_eq!(risk_of_options.guid(), Some("com.rune580.riskofoptions"));
+        assert_eq!(
+            risk_of_options.source_dll(),
+            Some(&PathBuf::from(
+                "C:\\Game\\BepInEx\\plugins\\RiskOfOptions.dll"
+            ))
+        );
+        assert_eq!(
+            risk_of_options.dependencies(),
+            &[
+                PluginDependency {
+                    guid: "com.bepis.r2api".to_string(),
+                    is_soft: false
+                },
+                PluginDependency {
+                    guid: "com.xoxfaby.BetterUI".to_string(),
+                    is_soft: true
+                },
+            ]
+        );
+        assert!(find(&tracker, "R2API").dependencies().is_empty());
+    }
+
+    #[test]
+    fn loading_log_makes_a_mod() {
+        let log =
+            BepInExLogEntry::new(LogLevel::Info, "[Info   :   BepInEx] Loading [R2API 5.0.5]");
+        let mod_ = BepInExMod::from_loading_log(&log).unwrap();
+
+        assert_eq!((mod_.name(), mod_.version()), ("R2API", "5.0.5"));
+        assert!(mod_.loading_timestamp().is_some());
+    }
+}
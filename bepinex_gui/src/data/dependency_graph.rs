@@ -0,0 +1,589 @@
+use std::collections::{HashMap, HashSet};
+
+use super::{
+    bepinex_mod::{BepInExMod, FailureReason, LoadStatus},
+    installed_mods::ModInventory,
+};
+
+// Installed in the game folder by the mod managers, not in `BepInEx/plugins`
+const BEPINEX_PACK_NAME: &str = "BepInExPack";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeKind {
+    // from the chainloader, by GUID
+    Plugin,
+    // from the `manifest.json` of a Thunderstore package, by `Author-Name`
+    Package,
+}
+
+#[derive(Debug, Clone)]
+pub struct GraphNode {
+    pub id: String,
+    pub name: String,
+    pub kind: NodeKind,
+    // `Author-Name` of the package a plugin comes from
+    pub package_id: Option<String>,
+    // something depends on it but it isn't installed, or didn't load
+    pub is_missing: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DependencyEdge {
+    // index of the node depending on `to`
+    pub from: usize,
+    pub to: usize,
+    // soft dependencies only change the load order
+    pub is_soft: bool,
+}
+
+/// What depends on what, from the plugins of the log and the manifests of `BepInEx/plugins`.
+///
+/// The packages the plugins got loaded from are merged into their plugin nodes.
+#[derive(Default)]
+pub struct DependencyGraph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<DependencyEdge>,
+    // node indices of each cycle, the chainloader can't order them
+    pub cycles: Vec<Vec<usize>>,
+}
+
+impl DependencyGraph {
+    pub fn build(mods: &[BepInExMod], inventory: &ModInventory) -> Self {
+        let mut graph = Self::default();
+
+        for mod_ in mods {
+            graph.add_node(plugin_id(mod_), mod_.name(), NodeKind::Plugin);
+        }
+
+        // a package whose plugins got loaded is shown as them, so that it isn't there twice
+        // and that the dependencies of its manifest reach them, by lowercase `Author-Name`
+        let mut package_nodes: HashMap<String, Vec<usize>> = HashMap::new();
+        for (package_index, package) in inventory.packages.iter().enumerate() {
+            let (Some(package_id), Some(manifest)) = (package.thunderstore_id(), &package.manifest)
+            else {
+                continue;
+            };
+            if package.is_disabled() {
+                continue;
+            }
+
+            let mut node_indices: Vec<usize> = mods
+                .iter()
+                .filter(|mod_| inventory.find_package_index(mod_) == Some(package_index))
+                .map(|mod_| graph.find_node(&plugin_id(mod_), NodeKind::Plugin))
+                .collect();
+            node_indices.sort_unstable();
+            node_indices.dedup();
+            for node_index in &node_indices {
+                graph.nodes[*node_index].package_id = Some(package_id.clone());
+            }
+            if node_indices.is_empty() {
+                graph.add_node(package_id.clone(), &manifest.name, NodeKind::Package);
+                node_indices.push(graph.find_node(&package_id, NodeKind::Package));
+            }
+
+            package_nodes.insert(package_id.to_lowercase(), node_indices);
+        }
+
+        for mod_ in mods {
+            let from = graph.find_node(&plugin_id(mod_), NodeKind::Plugin);
+            for dependency in mod_.dependencies() {
+                graph.add_edge(from, &dependency.guid, NodeKind::Plugin, dependency.is_soft);
+            }
+
+            // failed plugins don't come with their dependencies, only with the missing ones
+            if let LoadStatus::Failed(FailureReason::MissingDependencies(dependencies)) =
+                mod_.status()
+            {
+                for dependency in dependencies {
+                    graph.add_edge(from, dependency_guid(dependency), NodeKind::Plugin, false);
+                }
+            }
+        }
+
+        for package in &inventory.packages {
+            let (Some(package_id), Some(manifest)) = (package.thunderstore_id(), &package.manifest)
+            else {
+                continue;
+            };
+            let Some(from_node_indices) = package_nodes.get(&package_id.to_lowercase()).cloned()
+            else {
+                continue;
+            };
+
+            for dependency in &manifest.dependencies {
+                let dependency_id = dependency_package_id(dependency);
+                if dependency_id.ends_with(BEPINEX_PACK_NAME) {
+                    continue;
+                }
+
+                for from in &from_node_indices {
+                    match package_nodes.get(&dependency_id.to_lowercase()) {
+                        Some(to_node_indices) => {
+                            for to in to_node_indices {
+                                graph.push_edge(*from, *to, false);
+                            }
+                        }
+                        None => graph.add_edge(*from, dependency_id, NodeKind::Package, false),
+                    }
+                }
+            }
+        }
+
+        graph.cycles = graph.find_cycles();
+
+        graph
+    }
+
+    /// Edges from the node to what it depends on.
+    pub fn dependencies(&self, node_index: usize) -> impl Iterator<Item = &DependencyEdge> {
+        self.edges
+            .iter()
+            .filter(move |edge| edge.from == node_index)
+    }
+
+    /// Edges from what depends on the node to it.
+    pub fn dependents(&self, node_index: usize) -> impl Iterator<Item = &DependencyEdge> {
+        self.edges.iter().filter(move |edge| edge.to == node_index)
+    }
+
+    pub fn missing_dependencies(&self, node_index: usize) -> impl Iterator<Item = &GraphNode> {
+        self.dependencies(node_index)
+            .filter(|edge| !edge.is_soft)
+            .map(|edge| &self.nodes[edge.to])
+            .filter(|node| node.is_missing)
+    }
+
+    /// Indices of what the node depends on, directly or not.
+    pub fn all_dependencies(&self, node_index: usize) -> HashSet<usize> {
+        self.reachable(node_index, |edge| (edge.from, edge.to))
+    }
+
+    /// Indices of what depends on the node, directly or not.
+    pub fn all_dependents(&self, node_index: usize) -> HashSet<usize> {
+        self.reachable(node_index, |edge| (edge.to, edge.from))
+    }
+
+    fn reachable(
+        &self,
+        node_index: usize,
+        direction: impl Fn(&DependencyEdge) -> (usize, usize),
+    ) -> HashSet<usize> {
+        let mut reached = HashSet::new();
+        let mut to_visit = vec![node_index];
+
+        while let Some(visited_node_index) = to_visit.pop() {
+            for (from, to) in self.edges.iter().map(&direction) {
+                if from == visited_node_index && to != node_index && reached.insert(to) {
+                    to_visit.push(to);
+                }
+            }
+        }
+
+        reached
+    }
+
+    pub fn is_in_cycle(&self, node_index: usize) -> bool {
+        self.cycles.iter().any(|cycle| cycle.contains(&node_index))
+    }
+
+    /// Column of each node when drawn, dependencies left of their dependents.
+    pub fn depths(&self) -> Vec<usize> {
+        let mut depths = vec![0; self.nodes.len()];
+
+        // the edges inside of a cycle would keep pushing its nodes right, without them
+        // no path is longer than the node count
+        let edges: Vec<&DependencyEdge> = self
+            .edges
+            .iter()
+            .filter(|edge| {
+                !self
+                    .cycles
+                    .iter()
+                    .any(|cycle| cycle.contains(&edge.from) && cycle.contains(&edge.to))
+            })
+            .collect();
+        for _ in 0..self.nodes.len() {
+            let mut has_changed = false;
+            for edge in &edges {
+                if depths[edge.from] <= depths[edge.to] {
+                    depths[edge.from] = depths[edge.to] + 1;
+                    has_changed = true;
+                }
+            }
+            if !has_changed {
+                break;
+            }
+        }
+
+        depths
+    }
+
+    fn add_node(&mut self, id: String, name: &str, kind: NodeKind) {
+        if self.try_find_node(&id, kind).is_none() {
+            self.nodes.push(GraphNode {
+                id,
+                name: name.to_string(),
+                kind,
+                package_id: None,
+                is_missing: false,
+            });
+        }
+    }
+
+    fn add_edge(&mut self, from: usize, to_id: &str, kind: NodeKind, is_soft: bool) {
+        let to = match self.try_find_node(to_id, kind) {
+            Some(to) => to,
+            // optional and not installed, nothing wrong with it
+            None if is_soft => return,
+            None => {
+                self.nodes.push(GraphNode {
+                    id: to_id.to_string(),
+                    name: to_id.to_string(),
+                    kind,
+                    package_id: None,
+                    is_missing: true,
+                });
+                self.nodes.len() - 1
+            }
+        };
+
+        self.push_edge(from, to, is_soft);
+    }
+
+    fn push_edge(&mut self, from: usize, to: usize, is_soft: bool) {
+        if !self
+            .edges
+            .iter()
+            .any(|edge| edge.from == from && edge.to == to)
+        {
+            self.edges.push(DependencyEdge { from, to, is_soft });
+        }
+    }
+
+    // GUIDs and package ids are case sensitive for the loader, but not for the mod managers
+    fn try_find_node(&self, id: &str, kind: NodeKind) -> Option<usize> {
+        self.nodes
+            .iter()
+            .position(|node| node.kind == kind && node.id.eq_ignore_ascii_case(id))
+    }
+
+    fn find_node(&self, id: &str, kind: NodeKind) -> usize {
+        self.try_find_node(id, kind)
+            .expect("nodes are added before their edges")
+    }
+
+    // Strongly connected components with more than one node, or depending on themselves
+    fn find_cycles(&self) -> Vec<Vec<usize>> {
+        let mut tarjan = Tarjan {
+            graph: self,
+            next_index: 0,
+            indices: HashMap::new(),
+            low_links: HashMap::new(),
+            stack: Vec::new(),
+            components: Vec::new(),
+        };
+        for node_index in 0..self.nodes.len() {
+            if !tarjan.indices.contains_key(&node_index) {
+                tarjan.visit(node_index);
+            }
+        }
+
+        tarjan
+            .components
+            .into_iter()
+            .filter(|component| {
+                component.len() > 1
+                    || self
+                        .edges
+                        .iter()
+                        .any(|edge| edge.from == component[0] && edge.to == component[0])
+            })
+            .collect()
+    }
+}
+
+fn plugin_id(mod_: &BepInExMod) -> String {
+    mod_.guid().unwrap_or(mod_.name()).to_string()
+}
+
+// `com.bepis.r2api.items (v1.0.0 or newer)`
+fn dependency_guid(missing_dependency: &str) -> &str {
+    missing_dependency
+        .split(" (")
+        .next()
+        .unwrap_or_default()
+        .trim()
+}
+
+// `Author-Name-1.2.3`
+fn dependency_package_id(dependency: &str) -> &str {
+    dependency
+        .rsplit_once('-')
+        .map_or(dependency, |(dependency_id, _)| dependency_id)
+}
+
+struct Tarjan<'a> {
+    graph: &'a DependencyGraph,
+    next_index: usize,
+    indices: HashMap<usize, usize>,
+    low_links: HashMap<usize, usize>,
+    stack: Vec<usize>,
+    components: Vec<Vec<usize>>,
+}
+
+impl Tarjan<'_> {
+    fn visit(&mut self, node_index: usize) {
+        self.indices.insert(node_index, self.next_index);
+        self.low_links.insert(node_index, self.next_index);
+        self.next_index += 1;
+        self.stack.push(node_index);
+
+        let dependencies: Vec<usize> = self
+            .graph
+            .dependencies(node_index)
+            .map(|edge| edge.to)
+            .collect();
+        for dependency in dependencies {
+            if !self.indices.contains_key(&dependency) {
+                self.visit(dependency);
+                let low_link = self.low_links[&node_index].min(self.low_links[&dependency]);
+                self.low_links.insert(node_index, low_link);
+            } else if self.stack.contains(&dependency) {
+                let low_link = self.low_links[&node_index].min(self.indices[&dependency]);
+                self.low_links.insert(node_index, low_link);
+            }
+        }
+
+        if self.low_links[&node_index] == self.indices[&node_index] {
+            let mut component = Vec::new();
+            while let Some(stacked_node_index) = self.stack.pop() {
+                component.push(stacked_node_index);
+                if stacked_node_index == node_index {
+                    break;
+                }
+            }
+            self.components.push(component);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::data::{
+        bepinex_log::{BepInExLogEntry, LogLevel},
+        bepinex_mod::parser::PluginTracker,
+        installed_mods::{InstalledPackage, PackageManifest},
+    };
+
+    // `guid\tname\tversion\tdll path\tdependencies` lines, like the loader sends them
+    fn plugins(plugin_info_list_text: &str) -> Vec<BepInExMod> {
+        PluginTracker::default().push_plugin_info_list(plugin_info_list_text)
+    }
+
+    fn package(folder_name: &str, dll_name: &str, dependencies: &[&str]) -> InstalledPackage {
+        let path = PathBuf::from("/Game/BepInEx/plugins").join(folder_name);
+        InstalledPackage {
+            dlls: vec![path.join(dll_name)],
+            manifest: Some(PackageManifest {
+                name: folder_name.rsplit('-').next().unwrap().to_string(),
+                dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+                ..Default::default()
+            }),
+            path,
+            icon_path: None,
+            readme_path: None,
+            disabled_dlls: vec![],
+        }
+    }
+
+    fn inventory(packages: Vec<InstalledPackage>) -> ModInventory {
+        ModInventory {
+            plugins_directory: PathBuf::from("/Game/BepInEx/plugins"),
+            packages,
+        }
+    }
+
+    fn node_index(graph: &DependencyGraph, id: &str) -> usize {
+        graph
+            .nodes
+            .iter()
+            .position(|node| node.id == id)
+            .unwrap_or_else(|| panic!("{id} not in the graph"))
+    }
+
+    #[test]
+    fn finds_cycles_between_plugins() {
+        let mods = plugins(
+            "a\tA\t1.0.0\t\tb\n\
+             b\tB\t1.0.0\t\tc\n\
+             c\tC\t1.0.0\t\ta\n\
+             d\tD\t1.0.0\t\ta\n\
+             e\tE\t1.0.0\t\t",
+        );
+        let graph = DependencyGraph::build(&mods, &ModInventory::default());
+
+        assert_eq!(graph.cycles.len(), 1);
+        let mut cycle = graph.cycles[0].clone();
+        cycle.sort_unstable();
+        let mut expected = vec![
+            node_index(&graph, "a"),
+            node_index(&graph, "b"),
+            node_index(&graph, "c"),
+        ];
+        expected.sort_unstable();
+        assert_eq!(cycle, expected);
+        assert!(!graph.is_in_cycle(node_index(&graph, "d")));
+        assert!(!graph.is_in_cycle(node_index(&graph, "e")));
+    }
+
+    #[test]
+    fn plugin_depending_on_itself_is_a_cycle() {
+        let mods = plugins("a\tA\t1.0.0\t\ta\nb\tB\t1.0.0\t\ta");
+        let graph = DependencyGraph::build(&mods, &ModInventory::default());
+
+        assert_eq!(graph.cycles, vec![vec![node_index(&graph, "a")]]);
+        assert!(!graph.is_in_cycle(node_index(&graph, "b")));
+    }
+
+    #[test]
+    fn depths_put_dependencies_first() {
+        let mods = plugins(
+            "a\tA\t1.0.0\t\tb\n\
+             b\tB\t1.0.0\t\tc\n\
+             c\tC\t1.0.0\t\t\n\
+             d\tD\t1.0.0\t\tc",
+        );
+        let graph = DependencyGraph::build(&mods, &ModInventory::default());
+        let depths = graph.depths();
+
+        assert_eq!(depths[node_index(&graph, "a")], 2);
+        assert_eq!(depths[node_index(&graph, "b")], 1);
+        assert_eq!(depths[node_index(&graph, "c")], 0);
+        assert_eq!(depths[node_index(&graph, "d")], 1);
+    }
+
+    #[test]
+    fn depths_stop_on_cycles() {
+        let mods = plugins(
+            "a\tA\t1.0.0\t\tb\n\
+             b\tB\t1.0.0\t\ta\n\
+             c\tC\t1.0.0\t\t\n\
+             d\tD\t1.0.0\t\tc\n\
+             e\tE\t1.0.0\t\ta",
+        );
+        let graph = DependencyGraph::build(&mods, &ModInventory::default());
+        let depths = graph.depths();
+
+        assert_eq!(depths[node_index(&graph, "a")], 0);
+        assert_eq!(depths[node_index(&graph, "b")], 0);
+        assert_eq!(depths[node_index(&graph, "c")], 0);
+        assert_eq!(depths[node_index(&graph, "d")], 1);
+        assert_eq!(depths[node_index(&graph, "e")], 1);
+    }
+
+    #[test]
+    fn matches_guids_case_insensitively() {
+        let mods = plugins(
+            "com.example.Lib\tLib\t1.0.0\t\t\n\
+             com.example.user\tUser\t1.0.0\t\tCOM.EXAMPLE.LIB",
+        );
+        let graph = DependencyGraph::build(&mods, &ModInventory::default());
+
+        assert_eq!(graph.nodes.len(), 2);
+        let user = node_index(&graph, "com.example.user");
+        assert_eq!(
+            graph
+                .dependencies(user)
+                .map(|edge| edge.to)
+                .collect::<Vec<_>>(),
+            vec![node_index(&graph, "com.example.Lib")]
+        );
+        assert_eq!(graph.missing_dependencies(user).count(), 0);
+    }
+
+    #[test]
+    fn soft_dependencies_that_are_not_installed_are_left_out() {
+        let mods = plugins("a\tA\t1.0.0\t\tb?");
+        let graph = DependencyGraph::build(&mods, &ModInventory::default());
+
+        assert_eq!(graph.nodes.len(), 1);
+        assert!(graph.edges.is_empty());
+    }
+
+    #[test]
+    fn missing_dependencies_of_failed_plugins_are_stripped_of_their_version() {
+        let mut tracker = PluginTracker::default();
+        let mods = tracker.push_log(&BepInExLogEntry::new(
+            LogLevel::Error,
+            "[Error  :   BepInEx] Could not load [FasterPickups 1.2.0] because it has missing dependencies: com.bepis.r2api.items (v1.0.0 or newer), com.rune580.riskofoptions",
+        ));
+        let graph = DependencyGraph::build(&mods, &ModInventory::default());
+
+        let missing_ids: Vec<&str> = graph
+            .missing_dependencies(node_index(&graph, "FasterPickups"))
+            .map(|node| node.id.as_str())
+            .collect();
+        assert_eq!(
+            missing_ids,
+            vec!["com.bepis.r2api.items", "com.rune580.riskofoptions"]
+        );
+    }
+
+    #[test]
+    fn dependency_ids_are_stripped() {
+        assert_eq!(
+            dependency_guid("com.bepis.r2api.items (v1.0.0 or newer)"),
+            "com.bepis.r2api.items"
+        );
+        assert_eq!(
+            dependency_guid("com.rune580.riskofoptions"),
+            "com.rune580.riskofoptions"
+        );
+        assert_eq!(
+            dependency_package_id("tristanmcpherson-R2API-5.0.5"),
+            "tristanmcpherson-R2API"
+        );
+        assert_eq!(dependency_package_id("NoVersion"), "NoVersion");
+    }
+
+    #[test]
+    fn packages_are_merged_into_their_loaded_plugins() {
+        let mods = plugins(
+            "com.example.lib\tLib\t1.0.0\t/Game/BepInEx/plugins/Author-Lib/Lib.dll\t\n\
+             com.example.user\tUser\t1.0.0\t/Game/BepInEx/plugins/Author-User/User.dll\t",
+        );
+        let inventory = inventory(vec![
+            package("Author-Lib", "Lib.dll", &["bbepis-BepInExPack-5.4.2100"]),
+            package(
+                "Author-User",
+                "User.dll",
+                &["author-lib-1.0.0", "Other-Missing-2.0.0"],
+            ),
+            package("Author-Unloaded", "Unloaded.dll", &[]),
+        ]);
+        let graph = DependencyGraph::build(&mods, &inventory);
+
+        // 2 plugins, the package that didn't load and the missing one
+        assert_eq!(graph.nodes.len(), 4);
+        let lib = node_index(&graph, "com.example.lib");
+        let user = node_index(&graph, "com.example.user");
+        assert_eq!(graph.nodes[lib].package_id.as_deref(), Some("Author-Lib"));
+        assert_eq!(
+            graph.nodes[node_index(&graph, "Author-Unloaded")].kind,
+            NodeKind::Package
+        );
+
+        let mut user_dependencies: Vec<usize> =
+            graph.dependencies(user).map(|edge| edge.to).collect();
+        user_dependencies.sort_unstable();
+        let mut expected = vec![lib, node_index(&graph, "Other-Missing")];
+        expected.sort_unstable();
+        assert_eq!(user_dependencies, expected);
+        assert!(graph.nodes[node_index(&graph, "Other-Missing")].is_missing);
+        assert_eq!(graph.dependencies(lib).count(), 0);
+    }
+}
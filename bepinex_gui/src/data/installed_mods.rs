@@ -0,0 +1,278 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use super::bepinex_mod::BepInExMod;
+
+/// Appended to the DLLs of a disabled mod, same as r2modman.
+pub const DISABLED_EXTENSION: &str = "old";
+
+/// The `manifest.json` of a Thunderstore package.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct PackageManifest {
+    pub name: String,
+    pub version_number: String,
+    pub website_url: String,
+    pub description: String,
+    // `Author-Name-1.0.0`
+    pub dependencies: Vec<String>,
+}
+
+/// A folder of `BepInEx/plugins`, usually a package installed by a mod manager,
+/// or a DLL put directly in it.
+#[derive(Debug, Clone)]
+pub struct InstalledPackage {
+    pub path: PathBuf,
+    pub manifest: Option<PackageManifest>,
+    pub icon_path: Option<PathBuf>,
+    pub readme_path: Option<PathBuf>,
+    pub dlls: Vec<PathBuf>,
+    // `.dll.old`, renamed by a mod manager or the GUI so that BepInEx ignores them
+    pub disabled_dlls: Vec<PathBuf>,
+}
+
+impl InstalledPackage {
+    fn from_folder(folder: &Path) -> Self {
+        let manifest = fs::read(folder.join("manifest.json"))
+            .ok()
+            .and_then(|bytes| {
+                // some mod managers write it with a BOM
+                let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(&bytes);
+                match serde_json::from_slice::<PackageManifest>(bytes) {
+                    Ok(manifest) => Some(manifest),
+                    Err(err) => {
+                        tracing::warn!("Invalid manifest.json in {:?}: {}", folder, err);
+                        None
+                    }
+                }
+            });
+
+        let mut dlls = Vec::new();
+        let mut disabled_dlls = Vec::new();
+        find_dlls(folder, &mut dlls, &mut disabled_dlls);
+
+        Self {
+            path: folder.to_path_buf(),
+            manifest,
+            icon_path: existing_file(folder.join("icon.png")),
+            readme_path: existing_file(folder.join("README.md")),
+            dlls,
+            disabled_dlls,
+        }
+    }
+
+    fn from_dll(dll: &Path) -> Self {
+        let (dlls, disabled_dlls) = if is_dll(dll) {
+            (vec![dll.to_path_buf()], vec![])
+        } else {
+            (vec![], vec![dll.to_path_buf()])
+        };
+
+        Self {
+            path: dll.to_path_buf(),
+            manifest: None,
+            icon_path: None,
+            readme_path: None,
+            dlls,
+            disabled_dlls,
+        }
+    }
+
+    /// `Namespace-Name` on Thunderstore, from the folder mod managers install packages in.
+    pub fn thunderstore_id(&self) -> Option<String> {
+        let folder_name = file_name(&self.path);
+        (self.manifest.is_some() && folder_name.contains('-')).then_some(folder_name)
+    }
+
+    /// Path relative to `BepInEx/plugins`, the same whether it is enabled or not.
+    pub fn key(&self, plugins_directory: &Path) -> String {
+        let relative_path = self.path.strip_prefix(plugins_directory).unwrap_or(&self.path);
+        let key = relative_path.to_string_lossy().replace('\\', "/");
+
+        match is_disabled_dll(&self.path) {
+            true => key[..key.len() - DISABLED_EXTENSION.len() - 1].to_string(),
+            false => key,
+        }
+    }
+
+    /// All of its DLLs are disabled, BepInEx won't load anything from it.
+    pub fn is_disabled(&self) -> bool {
+        self.dlls.is_empty() && !self.disabled_dlls.is_empty()
+    }
+
+    /// Name from the manifest, or from the folder / DLL.
+    pub fn name(&self) -> String {
+        match &self.manifest {
+            Some(manifest) if !manifest.name.is_empty() => manifest.name.clone(),
+            _ => file_name(&self.path),
+        }
+    }
+
+    /// Whether the plugin got loaded from this package.
+    ///
+    /// Only the loaded plugins come with their DLL, the others are matched by name.
+    pub fn contains(&self, mod_: &BepInExMod) -> bool {
+        if let Some(source_dll) = mod_.source_dll() {
+            return self.dlls.iter().any(|dll| is_same_path(dll, source_dll))
+                || self.disabled_dlls.iter().any(|disabled_dll| {
+                    is_same_path(&disabled_dll.with_extension(""), source_dll)
+                });
+        }
+
+        let mod_name = normalize_name(mod_.name());
+        if mod_name.is_empty() {
+            return false;
+        }
+
+        self.manifest
+            .iter()
+            .map(|manifest| normalize_name(&manifest.name))
+            .chain(
+                self.dlls
+                    .iter()
+                    .chain(&self.disabled_dlls)
+                    .map(|dll| normalize_name(&dll_name(dll))),
+            )
+            // `Author-Name` folders of mod managers
+            .chain(file_name(&self.path).rsplit('-').next().map(normalize_name))
+            .any(|name| name == mod_name)
+    }
+}
+
+/// What is installed compared to what the chainloader told about.
+#[derive(Default)]
+pub struct ModInventory {
+    pub plugins_directory: PathBuf,
+    pub packages: Vec<InstalledPackage>,
+}
+
+impl ModInventory {
+    pub fn scan(bepinex_root: &Path) -> Self {
+        Self::scan_plugins_directory(bepinex_root.join("plugins"))
+    }
+
+    pub fn scan_plugins_directory(plugins_directory: PathBuf) -> Self {
+        let mut entries: Vec<PathBuf> = match fs::read_dir(&plugins_directory) {
+            Ok(entries) => entries.flatten().map(|entry| entry.path()).collect(),
+            Err(err) => {
+                tracing::warn!("Failed reading {:?}: {}", plugins_directory, err);
+                Vec::new()
+            }
+        };
+        entries.sort();
+
+        let packages = entries
+            .iter()
+            .filter_map(|path| {
+                if path.is_dir() {
+                    Some(InstalledPackage::from_folder(path))
+                } else if is_dll(path) || is_disabled_dll(path) {
+                    Some(InstalledPackage::from_dll(path))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        Self {
+            plugins_directory,
+            packages,
+        }
+    }
+
+    /// Index in [`Self::packages`] of the package the plugin comes from.
+    pub fn find_package_index(&self, mod_: &BepInExMod) -> Option<usize> {
+        self.packages
+            .iter()
+            .position(|package| package.contains(mod_))
+    }
+
+    /// Indices of the packages with DLLs that none of the plugins of the log came from.
+    pub fn not_loaded_package_indices(&self, mods: &[BepInExMod]) -> Vec<usize> {
+        (0..self.packages.len())
+            .filter(|package_index| {
+                let package = &self.packages[*package_index];
+                package.manifest.is_some()
+                    && !package.dlls.is_empty()
+                    && !mods.iter().any(|mod_| package.contains(mod_))
+            })
+            .collect()
+    }
+
+    /// DLLs that aren't part of a package, and that no plugin of the log came from.
+    pub fn orphaned_dlls(&self, mods: &[BepInExMod]) -> Vec<PathBuf> {
+        self.packages
+            .iter()
+            .filter(|package| package.manifest.is_none())
+            .filter(|package| !mods.iter().any(|mod_| package.contains(mod_)))
+            .flat_map(|package| package.dlls.iter().cloned())
+            .collect()
+    }
+}
+
+fn find_dlls(folder: &Path, dlls: &mut Vec<PathBuf>, disabled_dlls: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(folder) else {
+        return;
+    };
+
+    let mut paths: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+    paths.sort();
+
+    for path in paths {
+        if path.is_dir() {
+            find_dlls(&path, dlls, disabled_dlls);
+        } else if is_dll(&path) {
+            dlls.push(path);
+        } else if is_disabled_dll(&path) {
+            disabled_dlls.push(path);
+        }
+    }
+}
+
+fn is_dll(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("dll"))
+}
+
+pub fn is_disabled_dll(path: &Path) -> bool {
+    file_name(path)
+        .to_lowercase()
+        .ends_with(&format!(".dll.{DISABLED_EXTENSION}"))
+}
+
+fn existing_file(path: PathBuf) -> Option<PathBuf> {
+    path.is_file().then_some(path)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|file_name| file_name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+// `Name` of `Name.dll` and `Name.dll.old`
+fn dll_name(path: &Path) -> String {
+    let file_name = file_name(path);
+    let file_name_lowercase = file_name.to_lowercase();
+
+    let extension = [format!(".dll.{DISABLED_EXTENSION}"), ".dll".to_string()]
+        .into_iter()
+        .find(|extension| file_name_lowercase.ends_with(extension.as_str()))
+        .unwrap_or_default();
+    file_name[..file_name.len() - extension.len()].to_string()
+}
+
+// `Risk Of Options`, `RiskOfOptions` and `Risk_Of_Options` are the same mod
+fn normalize_name(name: &str) -> String {
+    name.to_lowercase().replace(['_', ' ', '-', '.'], "")
+}
+
+// Windows paths are case insensitive, and the loader may use either separator
+fn is_same_path(a: &Path, b: &Path) -> bool {
+    let normalize = |path: &Path| path.to_string_lossy().to_lowercase().replace('/', "\\");
+    normalize(a) == normalize(b)
+}
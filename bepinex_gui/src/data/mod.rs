@@ -1,2 +1,9 @@
 pub mod bepinex_log;
 pub mod bepinex_mod;
+pub mod dependency_graph;
+pub mod installed_mods;
+pub mod mod_actions;
+pub mod mod_list_export;
+pub mod mod_profiles;
+pub mod regression_report;
+pub mod session_history;
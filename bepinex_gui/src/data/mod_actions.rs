@@ -0,0 +1,353 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use strum::Display;
+
+use crate::paths;
+
+use super::installed_mods::{InstalledPackage, DISABLED_EXTENSION};
+
+const ACTION_LOG_FILE_NAME: &str = "mod_actions.json";
+// copies of the overwritten files, in the app data
+const FILE_BACKUPS_DIRECTORY_NAME: &str = "file_backups";
+
+// Older actions get dropped
+const MAX_ACTION_COUNT: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, Serialize, Deserialize)]
+pub enum ModActionKind {
+    Disabled,
+    Enabled,
+    #[strum(serialize = "Overwrote the configs for")]
+    OverwroteConfigs,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileRename {
+    pub from: PathBuf,
+    pub to: PathBuf,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileBackup {
+    pub path: PathBuf,
+    // copy of the file before it got overwritten, `None` when it didn't exist
+    pub backup: Option<PathBuf>,
+}
+
+/// Files the GUI renamed in `BepInEx/plugins` or overwrote in `BepInEx/config`, kept so that it can be undone.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModAction {
+    pub kind: ModActionKind,
+    pub package_name: String,
+    pub time: SystemTime,
+    pub renames: Vec<FileRename>,
+    #[serde(default)]
+    pub file_backups: Vec<FileBackup>,
+}
+
+impl ModAction {
+    pub fn is_in(&self, plugins_directory: &Path) -> bool {
+        // the configs are next to the plugins, in the same `BepInEx` folder
+        let bepinex_root = plugins_directory.parent().unwrap_or(plugins_directory);
+
+        !plugins_directory.as_os_str().is_empty()
+            && (!self.renames.is_empty() || !self.file_backups.is_empty())
+            && self
+                .renames
+                .iter()
+                .all(|rename| rename.from.starts_with(plugins_directory))
+            && self
+                .file_backups
+                .iter()
+                .all(|file_backup| file_backup.path.starts_with(bepinex_root))
+    }
+
+    // Once it can't be undone anymore
+    fn delete_backups(&self) {
+        for backup in self
+            .file_backups
+            .iter()
+            .filter_map(|file_backup| file_backup.backup.as_ref())
+        {
+            if let Err(err) = fs::remove_file(backup) {
+                tracing::warn!("Failed deleting the backup {:?}: {}", backup, err);
+            }
+        }
+    }
+}
+
+/// Renames the DLLs of the package to `.dll.old` so that BepInEx skips them on the next launch.
+pub fn disable(package: &InstalledPackage) -> io::Result<ModAction> {
+    let renames = package
+        .dlls
+        .iter()
+        .map(|dll| {
+            let mut disabled_dll = dll.clone().into_os_string();
+            disabled_dll.push(format!(".{DISABLED_EXTENSION}"));
+
+            FileRename {
+                from: dll.clone(),
+                to: disabled_dll.into(),
+            }
+        })
+        .collect();
+
+    apply(ModActionKind::Disabled, package, renames)
+}
+
+pub fn enable(package: &InstalledPackage) -> io::Result<ModAction> {
+    let renames = package
+        .disabled_dlls
+        .iter()
+        .map(|disabled_dll| FileRename {
+            from: disabled_dll.clone(),
+            to: disabled_dll.with_extension(""),
+        })
+        .collect();
+
+    apply(ModActionKind::Enabled, package, renames)
+}
+
+fn apply(
+    kind: ModActionKind,
+    package: &InstalledPackage,
+    renames: Vec<FileRename>,
+) -> io::Result<ModAction> {
+    if renames.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("No DLL to rename in {:?}", package.path),
+        ));
+    }
+
+    rename_all(&renames)?;
+
+    Ok(ModAction {
+        kind,
+        package_name: package.name(),
+        time: SystemTime::now(),
+        renames,
+        file_backups: Vec::new(),
+    })
+}
+
+/// Writes the config files of a profile, `files` being `(path, text)`.
+///
+/// What they were gets copied to the app data first, so that undoing puts them back.
+pub fn overwrite_configs(profile_name: &str, files: &[(PathBuf, &str)]) -> io::Result<ModAction> {
+    let time = SystemTime::now();
+    let backups_directory = paths::get_app_config_directory()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No app data directory"))?
+        .join(FILE_BACKUPS_DIRECTORY_NAME)
+        .join(
+            time.duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis()
+                .to_string(),
+        );
+    fs::create_dir_all(&backups_directory)?;
+
+    let mut file_backups = Vec::with_capacity(files.len());
+    for (i, (path, text)) in files.iter().enumerate() {
+        let result = backup_and_write(path, text, &backups_directory.join(i.to_string()));
+        match result {
+            Ok(file_backup) => file_backups.push(file_backup),
+            // all or nothing, like the renames
+            Err(err) => {
+                if let Err(restore_err) = restore_all(&file_backups) {
+                    tracing::error!("Failed restoring the configs: {}", restore_err);
+                }
+                return Err(io::Error::new(
+                    err.kind(),
+                    format!("Failed writing {path:?}: {err}"),
+                ));
+            }
+        }
+    }
+
+    Ok(ModAction {
+        kind: ModActionKind::OverwroteConfigs,
+        package_name: profile_name.to_string(),
+        time,
+        renames: Vec::new(),
+        file_backups,
+    })
+}
+
+fn backup_and_write(path: &Path, text: &str, backup: &Path) -> io::Result<FileBackup> {
+    let backup = match path.exists() {
+        true => {
+            fs::copy(path, backup)?;
+            Some(backup.to_path_buf())
+        }
+        false => None,
+    };
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, text)?;
+
+    Ok(FileBackup {
+        path: path.to_path_buf(),
+        backup,
+    })
+}
+
+// Newest first, the files that didn't exist get deleted
+fn restore_all(file_backups: &[FileBackup]) -> io::Result<()> {
+    for file_backup in file_backups.iter().rev() {
+        match &file_backup.backup {
+            Some(backup) => {
+                fs::copy(backup, &file_backup.path)?;
+            }
+            None => match fs::remove_file(&file_backup.path) {
+                Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+                _ => {}
+            },
+        }
+    }
+
+    Ok(())
+}
+
+/// Puts the files back where they were before the action, and the overwritten ones as they were.
+pub fn undo(action: &ModAction) -> io::Result<()> {
+    let reverted_renames: Vec<FileRename> = action
+        .renames
+        .iter()
+        .rev()
+        .map(|rename| FileRename {
+            from: rename.to.clone(),
+            to: rename.from.clone(),
+        })
+        .collect();
+
+    rename_all(&reverted_renames)?;
+    restore_all(&action.file_backups)
+}
+
+// All or nothing, the renames done before a failure are reverted
+fn rename_all(renames: &[FileRename]) -> io::Result<()> {
+    for (i, rename) in renames.iter().enumerate() {
+        let result = if rename.to.exists() {
+            Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{:?} already exists", rename.to),
+            ))
+        } else {
+            fs::rename(&rename.from, &rename.to)
+        };
+
+        if let Err(err) = result {
+            for done_rename in renames[..i].iter().rev() {
+                if let Err(revert_err) = fs::rename(&done_rename.to, &done_rename.from) {
+                    tracing::error!(
+                        "Failed reverting the rename of {:?}: {}",
+                        done_rename.from,
+                        revert_err
+                    );
+                }
+            }
+
+            return Err(io::Error::new(
+                err.kind(),
+                format!("Failed renaming {:?}: {err}", rename.from),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Every action done from the GUI, newest last, for all the games.
+#[derive(Default)]
+pub struct ActionLog {
+    actions: Vec<ModAction>,
+}
+
+impl ActionLog {
+    pub fn read() -> Self {
+        let actions = action_log_file_full_path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|text| match serde_json::from_str(&text) {
+                Ok(actions) => Some(actions),
+                Err(err) => {
+                    tracing::error!("Invalid {}: {}", ACTION_LOG_FILE_NAME, err);
+                    None
+                }
+            })
+            .unwrap_or_default();
+
+        Self { actions }
+    }
+
+    /// Actions on the mods of the given `BepInEx/plugins`, newest first.
+    pub fn actions_in<'a>(
+        &'a self,
+        plugins_directory: &'a Path,
+    ) -> impl Iterator<Item = &'a ModAction> {
+        self.actions
+            .iter()
+            .rev()
+            .filter(move |action| action.is_in(plugins_directory))
+    }
+
+    // Reread first, the General and Profiles tabs both write to it
+    pub fn push(&mut self, action: ModAction) {
+        *self = Self::read();
+
+        self.actions.push(action);
+        if self.actions.len() > MAX_ACTION_COUNT {
+            self.actions.remove(0).delete_backups();
+        }
+
+        self.write();
+    }
+
+    /// Reverts the newest action on the mods of the given `BepInEx/plugins`.
+    pub fn undo_last(&mut self, plugins_directory: &Path) -> io::Result<()> {
+        *self = Self::read();
+
+        let Some(action_index) = self
+            .actions
+            .iter()
+            .rposition(|action| action.is_in(plugins_directory))
+        else {
+            return Ok(());
+        };
+
+        undo(&self.actions[action_index])?;
+        self.actions.remove(action_index).delete_backups();
+        self.write();
+
+        Ok(())
+    }
+
+    fn write(&self) {
+        let Some(path) = action_log_file_full_path() else {
+            return;
+        };
+
+        let result = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| {
+                serde_json::to_string_pretty(&self.actions)
+                    .map_err(io::Error::from)
+                    .and_then(|json| fs::write(&path, json))
+            });
+        if let Err(err) = result {
+            tracing::error!("Failed writing {:?}: {}", path, err);
+        }
+    }
+}
+
+fn action_log_file_full_path() -> Option<PathBuf> {
+    paths::get_app_config_directory().map(|directory| directory.join(ACTION_LOG_FILE_NAME))
+}
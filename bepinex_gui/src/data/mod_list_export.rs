@@ -0,0 +1,94 @@
+use std::collections::BTreeSet;
+
+use serde::Serialize;
+use strum::{Display, EnumIter};
+
+use super::{
+    bepinex_mod::{BepInExMod, LoadStatus},
+    installed_mods::ModInventory,
+};
+
+/// How the mod list gets copied, for sharing it when asking for help.
+#[derive(Clone, Copy, PartialEq, Eq, Display, EnumIter)]
+pub enum ExportFormat {
+    #[strum(serialize = "Markdown (Discord)")]
+    Markdown,
+    #[strum(serialize = "JSON")]
+    Json,
+    // what goes in the `dependencies` of a modpack manifest
+    #[strum(serialize = "Thunderstore dependencies")]
+    ThunderstoreDependencies,
+}
+
+#[derive(Serialize)]
+struct ExportedMod<'a> {
+    name: &'a str,
+    version: &'a str,
+    guid: Option<&'a str>,
+    status: String,
+    // `Author-Name-1.2.3`, when installed by a mod manager
+    thunderstore_package: Option<String>,
+}
+
+pub fn export(format: ExportFormat, mods: &[BepInExMod], inventory: &ModInventory) -> String {
+    match format {
+        ExportFormat::Markdown => export_markdown(mods),
+        ExportFormat::Json => export_json(mods, inventory),
+        ExportFormat::ThunderstoreDependencies => export_thunderstore_dependencies(mods, inventory),
+    }
+}
+
+fn export_markdown(mods: &[BepInExMod]) -> String {
+    let mut markdown = format!("**Mods ({})**\n", mods.len());
+
+    for mod_ in mods {
+        markdown += &format!(
+            "- {} {} `{}`",
+            mod_.status().icon(),
+            mod_.name(),
+            mod_.version()
+        );
+        if let Some(guid) = mod_.guid() {
+            markdown += &format!(" ({guid})");
+        }
+        if !matches!(mod_.status(), LoadStatus::Loaded) {
+            markdown += &format!(" - {}", mod_.status());
+        }
+        markdown.push('\n');
+    }
+
+    markdown
+}
+
+fn export_json(mods: &[BepInExMod], inventory: &ModInventory) -> String {
+    let exported_mods: Vec<ExportedMod> = mods
+        .iter()
+        .map(|mod_| ExportedMod {
+            name: mod_.name(),
+            version: mod_.version(),
+            guid: mod_.guid(),
+            status: mod_.status().to_string(),
+            thunderstore_package: dependency_string(mod_, inventory),
+        })
+        .collect();
+
+    serde_json::to_string_pretty(&exported_mods).unwrap_or_default()
+}
+
+// Packages can hold several plugins, each one is listed once
+fn export_thunderstore_dependencies(mods: &[BepInExMod], inventory: &ModInventory) -> String {
+    let dependencies: BTreeSet<String> = mods
+        .iter()
+        .filter_map(|mod_| dependency_string(mod_, inventory))
+        .collect();
+
+    serde_json::to_string_pretty(&dependencies).unwrap_or_default()
+}
+
+fn dependency_string(mod_: &BepInExMod, inventory: &ModInventory) -> Option<String> {
+    let package = &inventory.packages[inventory.find_package_index(mod_)?];
+    let package_id = package.thunderstore_id()?;
+    let version = &package.manifest.as_ref()?.version_number;
+
+    Some(format!("{package_id}-{version}"))
+}
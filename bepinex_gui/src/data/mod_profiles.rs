@@ -0,0 +1,246 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs, io,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::paths;
+
+use super::{
+    installed_mods::ModInventory,
+    mod_actions::{self, ActionLog},
+};
+
+/// Which mods are enabled, and the configs of `BepInEx/config`, for one game folder.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModProfile {
+    pub name: String,
+    pub game_folder_full_path: PathBuf,
+    // see `AppLaunchConfig::bepinex_root`, each mod manager profile has its own
+    pub bepinex_root: PathBuf,
+    pub created_time: SystemTime,
+    // keys of the packages, see `InstalledPackage::key`
+    pub enabled_packages: BTreeSet<String>,
+    pub disabled_packages: BTreeSet<String>,
+    // path relative to `BepInEx/config` -> text of the file
+    pub config_files: BTreeMap<String, String>,
+}
+
+impl ModProfile {
+    pub fn snapshot(name: &str, game_folder_full_path: &Path, bepinex_root: &Path) -> Self {
+        let inventory = ModInventory::scan(bepinex_root);
+
+        let mut enabled_packages = BTreeSet::new();
+        let mut disabled_packages = BTreeSet::new();
+        for package in &inventory.packages {
+            let key = package.key(&inventory.plugins_directory);
+            if package.is_disabled() {
+                disabled_packages.insert(key);
+            } else if !package.dlls.is_empty() {
+                enabled_packages.insert(key);
+            }
+        }
+
+        let config_directory = config_directory(bepinex_root);
+        let mut config_files = BTreeMap::new();
+        read_config_files(&config_directory, &config_directory, &mut config_files);
+
+        Self {
+            name: name.to_string(),
+            game_folder_full_path: game_folder_full_path.to_path_buf(),
+            bepinex_root: bepinex_root.to_path_buf(),
+            created_time: SystemTime::now(),
+            enabled_packages,
+            disabled_packages,
+            config_files,
+        }
+    }
+
+    // Also the name of the profile file
+    pub fn id(&self) -> u128 {
+        self.created_time
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis()
+    }
+}
+
+/// What switching to a profile would change in the game folder.
+#[derive(Default)]
+pub struct ProfileDiff {
+    pub packages_to_enable: Vec<String>,
+    pub packages_to_disable: Vec<String>,
+    // in the profile but not installed anymore, left as is
+    pub missing_packages: Vec<String>,
+    // enabled but installed after the profile got saved, disabled too
+    pub new_packages: Vec<String>,
+    pub changed_config_files: Vec<String>,
+}
+
+impl ProfileDiff {
+    pub fn compare(profile: &ModProfile) -> Self {
+        let current =
+            ModProfile::snapshot("", &profile.game_folder_full_path, &profile.bepinex_root);
+        let mut diff = Self::default();
+
+        for key in &profile.enabled_packages {
+            if current.disabled_packages.contains(key) {
+                diff.packages_to_enable.push(key.clone());
+            } else if !current.enabled_packages.contains(key) {
+                diff.missing_packages.push(key.clone());
+            }
+        }
+        for key in &profile.disabled_packages {
+            if current.enabled_packages.contains(key) {
+                diff.packages_to_disable.push(key.clone());
+            } else if !current.disabled_packages.contains(key) {
+                diff.missing_packages.push(key.clone());
+            }
+        }
+        diff.new_packages = current
+            .enabled_packages
+            .iter()
+            .filter(|key| {
+                !profile.enabled_packages.contains(*key)
+                    && !profile.disabled_packages.contains(*key)
+            })
+            .cloned()
+            .collect();
+
+        diff.changed_config_files = profile
+            .config_files
+            .iter()
+            .filter(|(path, text)| current.config_files.get(*path) != Some(text))
+            .map(|(path, _)| path.clone())
+            .collect();
+
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.packages_to_enable.is_empty()
+            && self.packages_to_disable.is_empty()
+            && self.new_packages.is_empty()
+            && self.changed_config_files.is_empty()
+    }
+}
+
+/// Enables and disables the mods like the profile, then writes its configs.
+///
+/// The renames and the overwritten configs go through the action log,
+/// so that they can be undone from the General tab.
+pub fn apply(profile: &ModProfile, diff: &ProfileDiff) -> io::Result<()> {
+    let inventory = ModInventory::scan(&profile.bepinex_root);
+    let mut action_log = ActionLog::read();
+
+    for package in &inventory.packages {
+        let key = package.key(&inventory.plugins_directory);
+        if diff.packages_to_enable.contains(&key) {
+            action_log.push(mod_actions::enable(package)?);
+        } else if diff.packages_to_disable.contains(&key) || diff.new_packages.contains(&key) {
+            action_log.push(mod_actions::disable(package)?);
+        }
+    }
+
+    let config_directory = config_directory(&profile.bepinex_root);
+    let config_files: Vec<(PathBuf, &str)> = diff
+        .changed_config_files
+        .iter()
+        .map(|path| {
+            (
+                config_directory.join(path),
+                profile.config_files[path].as_str(),
+            )
+        })
+        .collect();
+    if !config_files.is_empty() {
+        action_log.push(mod_actions::overwrite_configs(
+            &profile.name,
+            &config_files,
+        )?);
+    }
+
+    Ok(())
+}
+
+pub fn profiles_directory() -> Option<PathBuf> {
+    paths::get_app_config_directory().map(|directory| directory.join("profiles"))
+}
+
+pub fn save(profile: &ModProfile) -> io::Result<()> {
+    let directory_full_path = profiles_directory()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No app data directory"))?;
+    fs::create_dir_all(&directory_full_path)?;
+
+    fs::write(
+        directory_full_path.join(format!("{}.json", profile.id())),
+        serde_json::to_string_pretty(profile)?,
+    )
+}
+
+pub fn delete(profile: &ModProfile) -> io::Result<()> {
+    let directory_full_path = profiles_directory()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No app data directory"))?;
+
+    fs::remove_file(directory_full_path.join(format!("{}.json", profile.id())))
+}
+
+/// Profiles of the `BepInEx` folder, oldest first.
+pub fn list(bepinex_root: &Path) -> Vec<ModProfile> {
+    let Some(entries) = profiles_directory().and_then(|directory| fs::read_dir(directory).ok())
+    else {
+        return Vec::new();
+    };
+
+    let mut profiles: Vec<ModProfile> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let text = fs::read_to_string(entry.path()).ok()?;
+            match serde_json::from_str::<ModProfile>(&text) {
+                Ok(profile) => Some(profile),
+                Err(err) => {
+                    tracing::error!("Invalid profile {:?}: {}", entry.path(), err);
+                    None
+                }
+            }
+        })
+        .filter(|profile| profile.bepinex_root == bepinex_root)
+        .collect();
+    profiles.sort_by_key(|profile| profile.created_time);
+
+    profiles
+}
+
+fn config_directory(bepinex_root: &Path) -> PathBuf {
+    bepinex_root.join("config")
+}
+
+fn read_config_files(
+    config_directory: &Path,
+    folder: &Path,
+    config_files: &mut BTreeMap<String, String>,
+) {
+    let Ok(entries) = fs::read_dir(folder) else {
+        return;
+    };
+
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.is_dir() {
+            read_config_files(config_directory, &path, config_files);
+        } else if path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("cfg"))
+        {
+            match fs::read_to_string(&path) {
+                Ok(text) => {
+                    let relative_path = path.strip_prefix(config_directory).unwrap_or(&path);
+                    config_files.insert(relative_path.to_string_lossy().replace('\\', "/"), text);
+                }
+                Err(err) => tracing::warn!("Failed reading {:?}: {}", path, err),
+            }
+        }
+    }
+}
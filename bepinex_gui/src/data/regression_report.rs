@@ -0,0 +1,101 @@
+use std::collections::{BTreeSet, HashMap};
+
+use super::{bepinex_mod::BepInExMod, session_history::SessionInfo};
+
+pub struct ModVersionChange {
+    pub name: String,
+    pub previous_version: String,
+    pub current_version: String,
+}
+
+pub struct ConfigValueChange {
+    pub key: String,
+    // `None` when the setting didn't exist on that side
+    pub previous_value: Option<String>,
+    pub current_value: Option<String>,
+}
+
+/// What changed between a previous session of the game and the current one.
+#[derive(Default)]
+pub struct RegressionReport {
+    pub added_mods: Vec<BepInExMod>,
+    pub removed_mods: Vec<BepInExMod>,
+    pub version_changes: Vec<ModVersionChange>,
+    // (signature, first line of the error)
+    pub new_errors: Vec<(String, String)>,
+    pub config_changes: Vec<ConfigValueChange>,
+}
+
+impl RegressionReport {
+    pub fn compare(previous: &SessionInfo, current: &SessionInfo) -> Self {
+        let previous_mods: HashMap<&str, &BepInExMod> = previous
+            .mods
+            .iter()
+            .map(|mod_| (mod_.name(), mod_))
+            .collect();
+        let current_mods: HashMap<&str, &BepInExMod> = current
+            .mods
+            .iter()
+            .map(|mod_| (mod_.name(), mod_))
+            .collect();
+
+        let mut report = Self::default();
+
+        for mod_ in &current.mods {
+            match previous_mods.get(mod_.name()) {
+                None => report.added_mods.push(mod_.clone()),
+                Some(previous_mod) if previous_mod.version() != mod_.version() => {
+                    report.version_changes.push(ModVersionChange {
+                        name: mod_.name().to_string(),
+                        previous_version: previous_mod.version().to_string(),
+                        current_version: mod_.version().to_string(),
+                    });
+                }
+                Some(_) => {}
+            }
+        }
+
+        report.removed_mods = previous
+            .mods
+            .iter()
+            .filter(|mod_| !current_mods.contains_key(mod_.name()))
+            .cloned()
+            .collect();
+
+        report.new_errors = current
+            .error_signatures
+            .iter()
+            .filter(|(signature, _)| !previous.error_signatures.contains_key(*signature))
+            .map(|(signature, title)| (signature.clone(), title.clone()))
+            .collect();
+
+        let config_keys: BTreeSet<&String> = previous
+            .config_values
+            .keys()
+            .chain(current.config_values.keys())
+            .collect();
+        report.config_changes = config_keys
+            .into_iter()
+            .filter_map(|key| {
+                let previous_value = previous.config_values.get(key);
+                let current_value = current.config_values.get(key);
+
+                (previous_value != current_value).then(|| ConfigValueChange {
+                    key: key.clone(),
+                    previous_value: previous_value.cloned(),
+                    current_value: current_value.cloned(),
+                })
+            })
+            .collect();
+
+        report
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added_mods.is_empty()
+            && self.removed_mods.is_empty()
+            && self.version_changes.is_empty()
+            && self.new_errors.is_empty()
+            && self.config_changes.is_empty()
+    }
+}
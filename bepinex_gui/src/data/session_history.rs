@@ -0,0 +1,296 @@
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use zip::{write::FileOptions, ZipArchive, ZipWriter};
+
+use crate::{
+    config::{cfg_file, launch::AppLaunchConfig},
+    paths,
+};
+
+use super::{
+    bepinex_log::{error_groups, BepInExLogEntry},
+    bepinex_mod::{self, BepInExMod},
+};
+
+const INFO_FILE_NAME: &str = "session.json";
+const LOGS_ZIP_FILE_NAME: &str = "logs.zip";
+// name of the log inside the zip, so that it opens like any other BepInEx log
+const LOGS_FILE_NAME: &str = "LogOutput.log";
+
+// Past that many matching lines, a session only counts them
+const MAX_SEARCH_HITS_PER_SESSION: usize = 5;
+
+/// What gets remembered of a game launch, next to its compressed logs.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SessionInfo {
+    pub target_name: String,
+    pub game_folder_full_path: PathBuf,
+    pub bepinex_version: String,
+    pub gui_version: String,
+    pub start_time: SystemTime,
+    // last save, the end of the session once the GUI got closed
+    pub end_time: SystemTime,
+    pub mods: Vec<BepInExMod>,
+    pub log_count: usize,
+    pub error_count: usize,
+    // error signature -> first line of its first occurrence
+    #[serde(default)]
+    pub error_signatures: BTreeMap<String, String>,
+    // `BepInEx.cfg [Section] Name` -> value, as they were at launch
+    #[serde(default)]
+    pub config_values: BTreeMap<String, String>,
+}
+
+impl SessionInfo {
+    /// Starts a session now, reading the BepInEx and GUI config files of the game.
+    pub fn new(app_launch_config: &AppLaunchConfig) -> Self {
+        let now = SystemTime::now();
+
+        let bepinex_cfg_full_path = app_launch_config
+            .bepinex_root()
+            .join("config")
+            .join("BepInEx.cfg");
+        let mut config_values = BTreeMap::new();
+        for cfg_file_full_path in [
+            &bepinex_cfg_full_path,
+            app_launch_config.bepinex_gui_csharp_cfg_full_path(),
+        ] {
+            let file_name = cfg_file_full_path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy();
+            match cfg_file::read_values(cfg_file_full_path) {
+                Ok(values) => config_values.extend(
+                    values
+                        .into_iter()
+                        .map(|(key, value)| (format!("{file_name} {key}"), value)),
+                ),
+                Err(err) => tracing::error!("Failed reading {:?}: {}", cfg_file_full_path, err),
+            }
+        }
+
+        Self {
+            target_name: app_launch_config.target_name().to_string(),
+            game_folder_full_path: app_launch_config.game_folder_full_path().clone(),
+            bepinex_version: app_launch_config.bepinex_version().to_string(),
+            gui_version: env!("CARGO_PKG_VERSION").to_string(),
+            start_time: now,
+            end_time: now,
+            mods: Vec::new(),
+            log_count: 0,
+            error_count: 0,
+            error_signatures: BTreeMap::new(),
+            config_values,
+        }
+    }
+
+    pub fn add_log(&mut self, log: &BepInExLogEntry) {
+        self.log_count += 1;
+
+        if error_groups::is_error_level(log.level()) {
+            self.error_count += 1;
+            self.error_signatures
+                .entry(error_groups::make_signature(log.message()))
+                .or_insert_with(|| log.message().lines().next().unwrap_or_default().to_string());
+        }
+    }
+
+    pub fn add_mod(&mut self, mod_: BepInExMod) {
+        bepinex_mod::upsert(&mut self.mods, mod_);
+    }
+
+    // Also the name of the session folder
+    pub fn id(&self) -> u128 {
+        self.start_time
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis()
+    }
+}
+
+/// A session saved on disk.
+#[derive(Clone)]
+pub struct StoredSession {
+    directory_full_path: PathBuf,
+    info: SessionInfo,
+}
+
+impl StoredSession {
+    pub const fn info(&self) -> &SessionInfo {
+        &self.info
+    }
+
+    /// The whole log of the session, as it would be in `LogOutput.log`.
+    pub fn read_logs(&self) -> io::Result<String> {
+        let zip_file = File::open(self.directory_full_path.join(LOGS_ZIP_FILE_NAME))?;
+        let mut archive = ZipArchive::new(zip_file)?;
+        let mut logs = String::new();
+        archive.by_name(LOGS_FILE_NAME)?.read_to_string(&mut logs)?;
+
+        Ok(logs)
+    }
+
+    /// Decompresses the log into the temp folder, for opening it in the log viewer.
+    pub fn extract_logs(&self) -> io::Result<PathBuf> {
+        let log_file_full_path = std::env::temp_dir().join(format!(
+            "BepInExGUI_session_{}_{LOGS_FILE_NAME}",
+            self.info.id()
+        ));
+        fs::write(&log_file_full_path, self.read_logs()?)?;
+
+        Ok(log_file_full_path)
+    }
+}
+
+/// Lines of a session matching a search.
+pub struct SearchHit {
+    pub session: StoredSession,
+    pub lines: Vec<String>,
+    pub match_count: usize,
+}
+
+pub fn sessions_directory() -> Option<PathBuf> {
+    paths::get_app_config_directory().map(|directory| directory.join("sessions"))
+}
+
+/// Writes (or overwrites) the session, logs being the text of each entry.
+pub fn save<'a>(info: &SessionInfo, logs: impl Iterator<Item = &'a str>) -> io::Result<()> {
+    let directory_full_path = sessions_directory()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No app data directory"))?
+        .join(info.id().to_string());
+    fs::create_dir_all(&directory_full_path)?;
+
+    write_atomically(&directory_full_path.join(LOGS_ZIP_FILE_NAME), |file| {
+        let mut zip = ZipWriter::new(file);
+        zip.start_file(
+            LOGS_FILE_NAME,
+            FileOptions::default().compression_method(zip::CompressionMethod::Deflated),
+        )?;
+        for log in logs {
+            zip.write_all(log.as_bytes())?;
+            zip.write_all(b"\n")?;
+        }
+
+        Ok(zip.finish()?)
+    })?;
+
+    // written last, a session without it is ignored
+    let info_text = serde_json::to_string_pretty(info)?;
+    write_atomically(&directory_full_path.join(INFO_FILE_NAME), |mut file| {
+        file.write_all(info_text.as_bytes())?;
+        Ok(file)
+    })
+}
+
+// Written next to the file then renamed over it,
+// so that a crash while saving leaves the previous save as it was
+fn write_atomically(
+    file_full_path: &Path,
+    write: impl FnOnce(File) -> io::Result<File>,
+) -> io::Result<()> {
+    let mut temp_file_full_path = file_full_path.as_os_str().to_owned();
+    temp_file_full_path.push(".tmp");
+
+    let file = write(File::create(&temp_file_full_path)?)?;
+    file.sync_all()?;
+    drop(file);
+
+    fs::rename(&temp_file_full_path, file_full_path)
+}
+
+/// The last session saved for the same game folder, other than `current`.
+pub fn find_previous(current: &SessionInfo) -> Option<StoredSession> {
+    list().into_iter().find(|session| {
+        session.info.id() != current.id()
+            && session.info.game_folder_full_path == current.game_folder_full_path
+    })
+}
+
+/// Saved sessions, most recent first.
+pub fn list() -> Vec<StoredSession> {
+    let Some(Ok(entries)) = sessions_directory().map(fs::read_dir) else {
+        return Vec::new();
+    };
+
+    let mut sessions: Vec<StoredSession> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| read_session(&entry.path()))
+        .collect();
+    sessions.sort_by_key(|session| std::cmp::Reverse(session.info.start_time));
+
+    sessions
+}
+
+fn read_session(directory_full_path: &Path) -> Option<StoredSession> {
+    let info_text = fs::read_to_string(directory_full_path.join(INFO_FILE_NAME)).ok()?;
+    match serde_json::from_str(&info_text) {
+        Ok(info) => Some(StoredSession {
+            directory_full_path: directory_full_path.to_path_buf(),
+            info,
+        }),
+        Err(err) => {
+            tracing::error!("Failed reading session {:?}: {}", directory_full_path, err);
+            None
+        }
+    }
+}
+
+/// Deletes the oldest sessions so that at most `max_session_count` are kept.
+pub fn apply_retention_limit(max_session_count: usize) {
+    for session in list().into_iter().skip(max_session_count) {
+        if let Err(err) = fs::remove_dir_all(&session.directory_full_path) {
+            tracing::error!(
+                "Failed deleting session {:?}: {}",
+                session.directory_full_path,
+                err
+            );
+        }
+    }
+}
+
+/// Case insensitive search through the logs of every saved session.
+pub fn search(query: &str) -> Vec<SearchHit> {
+    let query = query.to_lowercase();
+
+    list()
+        .into_iter()
+        .filter_map(|session| {
+            let logs = match session.read_logs() {
+                Ok(logs) => logs,
+                Err(err) => {
+                    tracing::error!(
+                        "Failed reading logs of session {}: {}",
+                        session.info.id(),
+                        err
+                    );
+                    return None;
+                }
+            };
+
+            let matching_lines: Vec<&str> = logs
+                .lines()
+                .filter(|line| line.to_lowercase().contains(&query))
+                .collect();
+            if matching_lines.is_empty() {
+                return None;
+            }
+
+            Some(SearchHit {
+                match_count: matching_lines.len(),
+                lines: matching_lines
+                    .into_iter()
+                    .take(MAX_SEARCH_HITS_PER_SESSION)
+                    .map(str::to_string)
+                    .collect(),
+                session,
+            })
+        })
+        .collect()
+}
@@ -21,6 +21,7 @@ fn main() {
 
     let args: Vec<String> = env::args().collect();
 
+    // without the loader arguments, the GUI is a log file viewer
     let init_config = AppLaunchConfig::from(&args).unwrap_or_default();
     let gui = app::BepInExGUI::new(init_config.clone());
 
@@ -1,4 +1,8 @@
 use eframe::{egui::*, *};
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumCount, EnumIter};
+
+use crate::data::bepinex_log::LogLevel;
 
 fn parse_color(color: &str) -> egui::Color32 {
     #![allow(clippy::identity_op)]
@@ -74,11 +78,14 @@ pub const fn small_rounding() -> f32 {
     4.0
 }
 
+fn design_tokens() -> serde_json::Value {
+    serde_json::from_str(include_str!("../assets/design_tokens.json"))
+        .expect("Failed to parse data/design_tokens.json")
+}
+
 // taken from https://github.com/rerun-io/rerun/blob/main/crates/re_ui/src/design_tokens.rs#L25
 pub fn get_dark_theme() -> egui::Style {
-    let json: serde_json::Value =
-        serde_json::from_str(include_str!("../assets/design_tokens.json"))
-            .expect("Failed to parse data/design_tokens.json");
+    let json = design_tokens();
 
     let mut egui_style = egui::Style {
         visuals: egui::Visuals::dark(),
@@ -191,6 +198,71 @@ pub fn get_dark_theme() -> egui::Style {
     egui_style
 }
 
+/// Colors of the console, one per log level plus the selection and the background.
+#[derive(
+    Debug, Clone, Copy, Display, PartialEq, Eq, Hash, EnumCount, EnumIter, Serialize, Deserialize,
+)]
+pub enum LogColorSlot {
+    Fatal,
+    Error,
+    Warning,
+    Message,
+    Info,
+    Debug,
+    Selection,
+    Background,
+}
+
+impl LogColorSlot {
+    pub const fn from_log_level(log_level: LogLevel) -> Self {
+        match log_level {
+            LogLevel::None | LogLevel::Fatal => Self::Fatal,
+            LogLevel::Error => Self::Error,
+            LogLevel::Warning => Self::Warning,
+            LogLevel::Message | LogLevel::All => Self::Message,
+            LogLevel::Info => Self::Info,
+            LogLevel::Debug => Self::Debug,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LogColors {
+    colors: [Color32; LogColorSlot::COUNT],
+}
+
+impl LogColors {
+    pub const fn get(&self, slot: LogColorSlot) -> Color32 {
+        self.colors[slot as usize]
+    }
+
+    pub fn set(&mut self, slot: LogColorSlot, color: Color32) {
+        self.colors[slot as usize] = color;
+    }
+
+    pub const fn level(&self, log_level: LogLevel) -> Color32 {
+        self.get(LogColorSlot::from_log_level(log_level))
+    }
+}
+
+/// Default console colors of the theme, from the `Alias.Color.Log` design tokens.
+pub fn get_log_colors(dark_mode: bool) -> LogColors {
+    use strum::IntoEnumIterator;
+
+    let json = design_tokens();
+    let theme_name = if dark_mode { "Dark" } else { "Light" };
+
+    let mut log_colors = LogColors {
+        colors: [Color32::TRANSPARENT; LogColorSlot::COUNT],
+    };
+    for slot in LogColorSlot::iter() {
+        let alias_path = format!("{{Alias.Color.Log.{theme_name}.{slot}.value}}");
+        log_colors.set(slot, get_aliased_color(&json, &alias_path));
+    }
+
+    log_colors
+}
+
 pub fn configure_fonts(ctx: &Context) {
     let mut font_def = FontDefinitions::default();
     font_def.font_data.extend([
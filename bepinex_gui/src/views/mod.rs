@@ -2,7 +2,7 @@ use std::path::PathBuf;
 
 use eframe::{
     self,
-    egui::{Context, TextStyle, TopBottomPanel, Ui, Visuals, Layout, RichText},
+    egui::{CentralPanel, Context, TextStyle, TopBottomPanel, Ui, Visuals, Layout, RichText},
     emath::{Vec2, Align},
 };
 use font_awesome::chars;
@@ -20,6 +20,7 @@ use self::components::{button, button_responsive_text};
 pub mod components;
 pub mod disclaimer;
 pub mod tabs;
+pub mod toasts;
 pub mod utils;
 
 impl BepInExGUI {
@@ -31,21 +32,67 @@ impl BepInExGUI {
                 ctx.set_visuals(Visuals::light());
             }
             self.config.update_text_styles(ctx);
+            self.config.update_log_colors();
 
             self.config.theme_just_changed = false;
         }
 
+        if self.app_launch_config.is_offline() {
+            self.update_log_file_to_open(ctx);
+        }
+
         if self.config.first_time {
             self.show_first_time_disclaimer(ctx);
         } else {
             self.render_header(ctx, frame);
 
-            let tab = &mut self.tabs[self.config.selected_tab_index];
+            if self.app_launch_config.is_offline() && self.app_launch_config.offline_log_file().is_none() {
+                self.render_open_log_file_prompt(ctx);
+            } else {
+                if self.config.console_filter_to_open.is_some() {
+                    if let Some(console_tab_index) = self.tabs.iter().position(|tab| tab.name() == "Console") {
+                        self.config.selected_tab_index = console_tab_index;
+                    }
+                }
+
+                let tab = &mut self.tabs[self.config.selected_tab_index];
+
+                tab.update(&self.app_launch_config, &mut self.config, ctx, frame);
+            }
+        }
+
+        if let Some(alerts) = &mut self.alerts {
+            toasts::show(alerts, &self.config, ctx);
+        }
+    }
+
+    fn update_log_file_to_open(&mut self, ctx: &Context) {
+        let dropped_file = ctx.input(|i| i.raw.dropped_files.iter().find_map(|file| file.path.clone()));
+        if dropped_file.is_some() {
+            self.config.log_file_to_open = dropped_file;
+        }
 
-            tab.update(&self.app_launch_config, &mut self.config, ctx, frame);
+        if let Some(log_file_full_path) = self.config.log_file_to_open.take() {
+            self.open_log_file(log_file_full_path);
         }
     }
 
+    fn render_open_log_file_prompt(&mut self, ctx: &Context) {
+        CentralPanel::default().show(ctx, |ui| {
+            ui.vertical_centered(|ui| {
+                ui.add_space(ui.available_height() / 3.);
+                ui.heading("Drop a LogOutput.log file here to view it");
+                ui.add_space(10.);
+
+                let text = RichText::new("Open Log File").heading();
+                let button_size = utils::egui::measure_widget_text(ui, text.clone()) + Vec2::new(40., 20.);
+                if button(text, ui, button_size).clicked() {
+                    self.config.log_file_to_open = file_explorer_utils::pick_log_file();
+                }
+            });
+        });
+    }
+
     fn show_first_time_disclaimer(&mut self, ctx: &Context) {
         disclaimer::show(&mut self.config, &mut self.disclaimer, ctx);
     }
@@ -53,7 +100,7 @@ impl BepInExGUI {
     fn render_header(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
         TopBottomPanel::top("top_panel").show(ctx, |ui| {
             ui.horizontal(|ui| {
-                let mut button_size = ui.available_size() / 3.;
+                let mut button_size = ui.available_size() / self.tabs.len() as f32;
                 button_size.y += 25.;
 
                 ui.spacing_mut().item_spacing.x = 1.;
@@ -69,6 +116,23 @@ impl BepInExGUI {
 
             ui.add_space(10.);
 
+            let is_tailing_file = self
+                .log_receiver_thread
+                .as_ref()
+                .is_some_and(|log_receiver| log_receiver.is_tailing_file());
+            if is_tailing_file {
+                ui.label(
+                    RichText::new("📄 File mode: could not connect to the game, showing its log file instead")
+                        .small()
+                        .color(ui.visuals().warn_fg_color),
+                )
+                .on_hover_text(
+                    "Something (firewall, antivirus, another program using the port...) is blocking the connection to BepInEx.GUI.Loader.\n\
+                     Logs appear with a small delay, once BepInEx writes them to LogOutput.log.",
+                );
+                ui.add_space(4.);
+            }
+
             if !self.config.first_time_console_disclaimer {
                 self.tabs[self.config.selected_tab_index].update_top_panel(
                     &self.app_launch_config,
@@ -0,0 +1,398 @@
+use std::path::{Path, PathBuf};
+
+use eframe::egui::{
+    Button, CentralPanel, CollapsingHeader, ComboBox, Context, Grid, RichText, ScrollArea,
+    SelectableLabel, SidePanel, TextEdit, Ui,
+};
+
+use crate::{
+    backend::process,
+    config::{
+        cfg_file::{self, CfgEntry, CfgFile, CfgValueKind},
+        launch::AppLaunchConfig,
+        Config,
+    },
+};
+
+use super::Tab;
+
+const SIDE_PANEL_WIDTH: f32 = 260.;
+const VALUE_WIDTH: f32 = 240.;
+
+pub struct ConfigsTab {
+    // read from disk when the tab gets shown, `None` until then
+    cfg_file_paths: Option<Vec<PathBuf>>,
+    cfg_file: Option<CfgFile>,
+    search_text: String,
+    // (text, is_error) of the last save
+    status: Option<(String, bool)>,
+}
+
+impl ConfigsTab {
+    pub const fn new() -> Self {
+        Self {
+            cfg_file_paths: None,
+            cfg_file: None,
+            search_text: String::new(),
+            status: None,
+        }
+    }
+
+    fn render(&mut self, data: &AppLaunchConfig, ctx: &Context) {
+        if data.game_folder_full_path().as_os_str().is_empty() {
+            CentralPanel::default().show(ctx, |ui| {
+                ui.centered_and_justified(|ui| {
+                    ui.heading("No game folder to edit the configs of");
+                });
+            });
+            return;
+        }
+
+        let config_directory = config_directory(data);
+
+        SidePanel::left("configs_side_panel")
+            .resizable(true)
+            .default_width(SIDE_PANEL_WIDTH)
+            .show(ctx, |ui| {
+                ui.add(TextEdit::singleline(&mut self.search_text).hint_text("Search"));
+                ui.add_space(5.);
+
+                ScrollArea::vertical()
+                    .auto_shrink([false; 2])
+                    .show(ui, |ui| {
+                        self.render_cfg_file_list(&config_directory, ui);
+                    });
+            });
+
+        CentralPanel::default().show(ctx, |ui| {
+            if let Some((text, is_error)) = &self.status {
+                let color = match is_error {
+                    true => ui.visuals().error_fg_color,
+                    false => ui.visuals().hyperlink_color,
+                };
+                ui.label(RichText::new(text).color(color));
+                ui.separator();
+            }
+
+            let Some(cfg_file) = &mut self.cfg_file else {
+                ui.centered_and_justified(|ui| {
+                    ui.heading("Pick a config file to edit");
+                });
+                return;
+            };
+
+            let mut should_save = false;
+            let mut should_discard = false;
+            // BepInEx already accepted what is in the file
+            let has_errors = cfg_file
+                .entries
+                .iter()
+                .filter(|entry| entry.is_changed())
+                .any(|entry| entry.validate(&entry.value).is_some());
+            ui.horizontal(|ui| {
+                ui.heading(relative_path(&config_directory, &cfg_file.path));
+
+                let can_save = cfg_file.is_changed() && !has_errors;
+                should_save = ui
+                    .add_enabled(can_save, Button::new("Save"))
+                    .on_disabled_hover_text(match has_errors {
+                        true => "Some values are invalid",
+                        false => "Nothing changed",
+                    })
+                    .clicked();
+                should_discard = ui
+                    .add_enabled(cfg_file.is_changed(), Button::new("Discard changes"))
+                    .clicked();
+            });
+            ui.separator();
+
+            ScrollArea::vertical()
+                .auto_shrink([false; 2])
+                .show(ui, |ui| {
+                    render_cfg_file(cfg_file, ui);
+                });
+
+            if should_save {
+                self.save(data);
+            } else if should_discard {
+                self.open(self.cfg_file.as_ref().map(|cfg_file| cfg_file.path.clone()));
+            }
+        });
+    }
+
+    // Other files can't be picked until the changes are saved or discarded
+    fn render_cfg_file_list(&mut self, config_directory: &Path, ui: &mut Ui) {
+        let cfg_file_paths = self
+            .cfg_file_paths
+            .get_or_insert_with(|| cfg_file::find_cfg_files(config_directory));
+        if cfg_file_paths.is_empty() {
+            ui.label(RichText::new("No config file yet, they get made when the mods load").weak());
+            return;
+        }
+
+        let search_text = self.search_text.trim().to_lowercase();
+        let is_changed = self.cfg_file.as_ref().is_some_and(CfgFile::is_changed);
+        let mut clicked_cfg_file_path = None;
+        for cfg_file_path in cfg_file_paths.iter() {
+            let name = relative_path(config_directory, cfg_file_path);
+            if !name.to_lowercase().contains(&search_text) {
+                continue;
+            }
+
+            let is_selected = self
+                .cfg_file
+                .as_ref()
+                .is_some_and(|cfg_file| &cfg_file.path == cfg_file_path);
+            if ui
+                .add_enabled(
+                    is_selected || !is_changed,
+                    SelectableLabel::new(is_selected, name),
+                )
+                .on_disabled_hover_text("Save or discard the changes first")
+                .clicked()
+            {
+                clicked_cfg_file_path = Some(cfg_file_path.clone());
+            }
+        }
+
+        if clicked_cfg_file_path.is_some() {
+            self.open(clicked_cfg_file_path);
+        }
+    }
+
+    fn open(&mut self, cfg_file_path: Option<PathBuf>) {
+        self.status = None;
+        self.cfg_file =
+            cfg_file_path.and_then(|cfg_file_path| match CfgFile::read(&cfg_file_path) {
+                Ok(cfg_file) => Some(cfg_file),
+                Err(err) => {
+                    self.status = Some((format!("Failed reading {cfg_file_path:?}: {err}"), true));
+                    None
+                }
+            });
+    }
+
+    fn save(&mut self, data: &AppLaunchConfig) {
+        let Some(cfg_file) = &mut self.cfg_file else {
+            return;
+        };
+
+        if data.is_offline() {
+            self.status = Some((
+                "Can't tell whether the game is running while viewing a log file".to_string(),
+                true,
+            ));
+            return;
+        }
+
+        // the mods save their configs while running, the changes would get lost
+        if process::is_game_running(data) {
+            self.status = Some((
+                "The game is running, close it before changing its configs".to_string(),
+                true,
+            ));
+            return;
+        }
+
+        self.status = Some(match cfg_file.write() {
+            Ok(_) => (
+                "Saved, the changes apply on the next launch".to_string(),
+                false,
+            ),
+            Err(err) => (format!("Failed saving {:?}: {err}", cfg_file.path), true),
+        });
+    }
+}
+
+fn render_cfg_file(cfg_file: &mut CfgFile, ui: &mut Ui) {
+    if cfg_file.entries.is_empty() {
+        ui.label(RichText::new("No setting in this file").weak());
+        return;
+    }
+
+    let sections: Vec<(String, Vec<usize>)> = cfg_file
+        .sections()
+        .into_iter()
+        .map(|(section, entry_indices)| (section.to_string(), entry_indices))
+        .collect();
+    for (section, entry_indices) in sections {
+        CollapsingHeader::new(RichText::new(&section).strong())
+            .id_source(("cfg_section", &section))
+            .default_open(true)
+            .show(ui, |ui| {
+                Grid::new(("cfg_section_grid", &section))
+                    .num_columns(3)
+                    .striped(true)
+                    .spacing([20., 6.])
+                    .show(ui, |ui| {
+                        for entry_index in entry_indices {
+                            render_entry(&mut cfg_file.entries[entry_index], entry_index, ui);
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+}
+
+fn render_entry(entry: &mut CfgEntry, entry_index: usize, ui: &mut Ui) {
+    let mut name_text = RichText::new(&entry.name);
+    if entry.is_changed() {
+        name_text = name_text.strong();
+    }
+    ui.label(name_text).on_hover_text(entry_details(entry));
+
+    ui.vertical(|ui| {
+        ui.set_max_width(VALUE_WIDTH);
+        render_value_widget(entry, entry_index, ui);
+
+        if let Some(error) = entry.validate(&entry.value) {
+            ui.label(
+                RichText::new(error)
+                    .small()
+                    .color(ui.visuals().error_fg_color),
+            );
+        }
+    });
+
+    match &entry.default_value {
+        Some(default_value) if *default_value != entry.value => {
+            if ui
+                .small_button("Reset")
+                .on_hover_text(format!("Back to the default value: {default_value}"))
+                .clicked()
+            {
+                entry.value = default_value.clone();
+            }
+        }
+        _ => {
+            ui.label("");
+        }
+    }
+}
+
+fn render_value_widget(entry: &mut CfgEntry, entry_index: usize, ui: &mut Ui) {
+    match entry.kind.clone() {
+        CfgValueKind::Boolean => {
+            let mut is_checked = entry.value.trim().eq_ignore_ascii_case("true");
+            if ui.checkbox(&mut is_checked, "").changed() {
+                entry.value = is_checked.to_string();
+            }
+        }
+        CfgValueKind::Enum(acceptable_values) => {
+            ComboBox::from_id_source(("cfg_enum", entry_index))
+                .selected_text(&entry.value)
+                .width(VALUE_WIDTH)
+                .show_ui(ui, |ui| {
+                    for acceptable_value in acceptable_values {
+                        ui.selectable_value(
+                            &mut entry.value,
+                            acceptable_value.clone(),
+                            &acceptable_value,
+                        );
+                    }
+                });
+        }
+        CfgValueKind::Flags(acceptable_values) => {
+            let flags: Vec<&str> = entry.value.split(',').map(str::trim).collect();
+            let mut checked_flags: Vec<bool> = acceptable_values
+                .iter()
+                .map(|acceptable_value| flags.contains(&acceptable_value.as_str()))
+                .collect();
+
+            let mut changed = false;
+            ui.horizontal_wrapped(|ui| {
+                for (acceptable_value, is_checked) in
+                    acceptable_values.iter().zip(&mut checked_flags)
+                {
+                    changed |= ui.checkbox(is_checked, acceptable_value).changed();
+                }
+            });
+
+            if changed {
+                entry.value = acceptable_values
+                    .iter()
+                    .zip(checked_flags)
+                    .filter(|(_, is_checked)| *is_checked)
+                    .map(|(acceptable_value, _)| acceptable_value.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+            }
+        }
+        CfgValueKind::Integer
+        | CfgValueKind::UnsignedInteger
+        | CfgValueKind::Float
+        | CfgValueKind::Text => {
+            let mut text_edit = TextEdit::singleline(&mut entry.value).desired_width(VALUE_WIDTH);
+            if let Some((min, max)) = entry.range {
+                text_edit = text_edit.hint_text(format!("{min} to {max}"));
+            }
+            ui.add(text_edit);
+        }
+    }
+}
+
+// The comments BepInEx wrote above the setting
+fn entry_details(entry: &CfgEntry) -> String {
+    let mut details = entry.description.clone();
+
+    if let Some(setting_type) = &entry.setting_type {
+        details += &format!("\n\nType: {setting_type}");
+    }
+    if let Some(default_value) = &entry.default_value {
+        details += &format!("\nDefault value: {default_value}");
+    }
+    if let Some((min, max)) = entry.range {
+        details += &format!("\nFrom {min} to {max}");
+    }
+
+    details.trim().to_string()
+}
+
+fn config_directory(data: &AppLaunchConfig) -> PathBuf {
+    data.bepinex_root().join("config")
+}
+
+fn relative_path(config_directory: &Path, cfg_file_path: &Path) -> String {
+    cfg_file_path
+        .strip_prefix(config_directory)
+        .unwrap_or(cfg_file_path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+impl Tab for ConfigsTab {
+    fn name(&self) -> &str {
+        "Configs"
+    }
+
+    fn update_top_panel(
+        &mut self,
+        _data: &AppLaunchConfig,
+        _gui_config: &mut Config,
+        ui: &mut eframe::egui::Ui,
+    ) {
+        ui.horizontal(|ui| {
+            ui.label("Settings of the mods, from BepInEx/config");
+
+            let is_changed = self.cfg_file.as_ref().is_some_and(CfgFile::is_changed);
+            if ui
+                .add_enabled(!is_changed, Button::new("Refresh"))
+                .on_disabled_hover_text("Save or discard the changes first")
+                .clicked()
+            {
+                self.cfg_file_paths = None;
+                self.open(self.cfg_file.as_ref().map(|cfg_file| cfg_file.path.clone()));
+            }
+        });
+    }
+
+    fn update(
+        &mut self,
+        data: &AppLaunchConfig,
+        _gui_config: &mut Config,
+        ctx: &Context,
+        _frame: &mut eframe::Frame,
+    ) {
+        self.render(data, ctx);
+    }
+}
@@ -1,6 +1,13 @@
 use clipboard::*;
 use crossbeam_channel::Receiver;
-use eframe::{egui::*, *};
+use eframe::{
+    egui::{
+        text::{CCursor, CCursorRange, LayoutJob},
+        text_edit::TextEditState,
+        *,
+    },
+    *,
+};
 use std::{
     collections::HashMap,
     sync::{
@@ -11,17 +18,33 @@ use std::{
 };
 
 use crate::{
-    backend::process,
-    config::{launch::AppLaunchConfig, Config},
+    backend::{
+        file_explorer_utils,
+        network::commands::{CommandChannel, ConsoleCommand},
+        process,
+    },
+    config::{launch::AppLaunchConfig, Config, ConsolePaneConfig},
     data::{
-        bepinex_log::{BepInExLogEntry, LogLevel},
-        bepinex_mod::BepInExMod,
+        bepinex_log::{
+            ansi::AnsiSpan, error_groups::ErrorGroups, rules::RuleOutcome, BepInExLogEntry,
+            LogLevel,
+        },
+        bepinex_mod::{self, BepInExMod},
+    },
+    theme::LogColorSlot,
+    views::{
+        self,
+        components::button,
+        disclaimer::Disclaimer,
+        utils::{egui::measure_widget_text, format_session_time, format_with_thousands_separator},
     },
-    views::{self, disclaimer::Disclaimer, components::button, utils::egui::measure_widget_text},
 };
 
 use super::Tab;
 
+const MAX_COMMAND_HISTORY_LENGTH: usize = 100;
+const MAX_COMMAND_SUGGESTION_COUNT: usize = 8;
+
 struct LogSelection {
     pub button_currently_down: bool,
     pub button_just_got_down: bool,
@@ -93,40 +116,85 @@ impl LogSelection {
 }
 
 struct Filter {
-    text: String,
+    // the text itself is in the pane config
     text_lowercase: String,
     pub selected_index_in_mods_combo_box: usize,
+    // only show the occurrences of this error group
+    error_group_index: Option<usize>,
 }
 
 struct Scroll {
     pending_scroll: Option<Vec2>,
+    // stick to the bottom when new logs come in
+    following: bool,
+    // logs received while not following
+    unread_log_count: usize,
+    jump_to_bottom: bool,
+    // from the last frame, used for jumping to the bottom
+    max_offset: f32,
 }
 
-pub struct ConsoleTab {
-    disclaimer: Disclaimer,
+impl Scroll {
+    fn follow(&mut self) {
+        self.following = true;
+        self.unread_log_count = 0;
+        self.jump_to_bottom = true;
+    }
+
+    fn pause(&mut self) {
+        self.following = false;
+    }
+}
+
+// Panes get too narrow to be useful past that
+const MAX_PANE_COUNT: usize = 4;
+
+/// Input line of the commands sent to the game.
+#[derive(Default)]
+struct CommandLine {
+    text: String,
+    // position in the history while browsing it with the arrow keys
+    history_index: Option<usize>,
+}
+
+/// Logs shared by all the console panes.
+#[derive(Default)]
+struct LogStore {
+    logs: Vec<BepInExLogEntry>,
+    error_groups: ErrorGroups,
+    // one per log, from the user rules
+    rule_outcomes: Vec<RuleOutcome>,
+    rules_generation: Option<u64>,
+}
+
+/// One view of the log store, with its own filters, scroll and selection.
+struct ConsolePane {
     log_selection: LogSelection,
     filter: Filter,
     scroll: Scroll,
+    // per pane because they depend on its width
+    log_heights: HashMap<usize, f32>,
+}
+
+pub struct ConsoleTab {
+    disclaimer: Disclaimer,
+    panes: Vec<ConsolePane>,
+    // the one driven by the top panel filters, the footer and the error groups
+    active_pane_index: usize,
     target_process_paused: bool,
     mod_receiver: Receiver<BepInExMod>,
     mods: Vec<BepInExMod>,
     log_receiver: Receiver<BepInExLogEntry>,
-    logs: Vec<BepInExLogEntry>,
+    store: LogStore,
+    show_error_groups: bool,
+    command_channel: CommandChannel,
+    command_line: CommandLine,
     should_exit_app: Arc<AtomicBool>,
-    log_heights: HashMap<usize, f32>,
 }
 
-impl ConsoleTab {
-    pub fn new(
-        mod_receiver: Receiver<BepInExMod>,
-        log_receiver: Receiver<BepInExLogEntry>,
-        should_exit_app: Arc<AtomicBool>,
-    ) -> Self {
+impl ConsolePane {
+    fn new(pane_config: &ConsolePaneConfig) -> Self {
         Self {
-            disclaimer: Disclaimer {
-                first_time_showing_it: true,
-                time_when_disclaimer_showed_up: None,
-            },
             log_selection: LogSelection {
                 button_currently_down: false,
                 button_just_got_down: false,
@@ -136,46 +204,77 @@ impl ConsoleTab {
                 cursor_pos_when_button_was_pressed: None,
             },
             filter: Filter {
-                text: Default::default(),
-                text_lowercase: Default::default(),
+                text_lowercase: pane_config.filter_text.to_lowercase(),
                 selected_index_in_mods_combo_box: 0,
+                error_group_index: None,
             },
             scroll: Scroll {
                 pending_scroll: None,
+                following: true,
+                unread_log_count: 0,
+                jump_to_bottom: false,
+                max_offset: 0.,
             },
-            target_process_paused: false,
-            mod_receiver,
-            mods: vec![BepInExMod::new("", "")],
-            log_receiver,
-            logs: vec![],
-            should_exit_app,
             log_heights: HashMap::new(),
         }
     }
 
-    fn render(&mut self, gui_config: &Config, ctx: &Context) {
-        CentralPanel::default().show(ctx, |ui| {
-            if self.logs.is_empty() {
-                render_loading_text(ui);
-            } else {
-                self.render_console_scroll_area(ui, gui_config);
-            }
-        });
+    fn clear_selection(&mut self) {
+        self.log_selection.index_of_first_selected_log = usize::MAX;
+        self.log_selection.index_of_last_selected_log = usize::MAX;
+    }
+
+    fn is_log_selected(&self, log_index: usize) -> bool {
+        is_between(
+            log_index,
+            self.log_selection.index_of_first_selected_log,
+            self.log_selection.index_of_last_selected_log,
+        )
+    }
+
+    fn is_log_visible(&self, pane_config: &ConsolePaneConfig, store: &LogStore, log_index: usize) -> bool {
+        let log = &store.logs[log_index];
+        let rule_outcome = store.rule_outcomes[log_index];
+
+        let log_level = rule_outcome.level.unwrap_or(log.level());
+        if log_level > pane_config.log_level_filter || rule_outcome.hide {
+            return false;
+        }
+
+        let error_group = self
+            .filter
+            .error_group_index
+            .and_then(|group_index| store.error_groups.get(group_index));
+        if error_group.is_some_and(|group| !group.contains(log_index)) {
+            return false;
+        }
+
+        does_log_match_text_filter(&self.filter.text_lowercase, log)
     }
 
-    fn render_console_scroll_area(&mut self, ui: &mut Ui, gui_config: &Config) {
+    fn render_console_scroll_area(
+        &mut self,
+        pane_index: usize,
+        ui: &mut Ui,
+        gui_config: &Config,
+        pane_config: &ConsolePaneConfig,
+        store: &LogStore,
+    ) {
         ui.spacing_mut().scroll_bar_width = 16.;
+        ui.visuals_mut().selection.bg_fill = gui_config.log_colors.get(LogColorSlot::Selection);
 
-        let scroll_area = ScrollArea::vertical()
+        let mut scroll_area = ScrollArea::vertical()
+            .id_source(("console_pane", pane_index))
             .drag_to_scroll(false)
             .auto_shrink([false; 2])
-            .stick_to_bottom(true)
-            .show(ui, |ui| {
-                if self.log_selection.button_just_got_down {
-                    self.logs.iter_mut().for_each(|log| log.is_selected = false);
-                }
+            .stick_to_bottom(self.scroll.following);
+        if self.scroll.jump_to_bottom {
+            scroll_area = scroll_area.vertical_scroll_offset(self.scroll.max_offset);
+            self.scroll.jump_to_bottom = false;
+        }
 
-                self.render_logs(gui_config, ui);
+        let scroll_area = scroll_area.show(ui, |ui| {
+                self.render_logs(gui_config, pane_config, store, ui);
 
                 if let Some(scroll) = self.scroll.pending_scroll {
                     ui.scroll_with_delta(scroll);
@@ -184,6 +283,60 @@ impl ConsoleTab {
             });
 
         self.auto_scroll_to_selection(&scroll_area, ui);
+
+        self.update_follow_state(&scroll_area, ui);
+
+        if !self.scroll.following && self.scroll.unread_log_count > 0 {
+            self.render_jump_to_bottom_button(pane_index, scroll_area.inner_rect, ui);
+        }
+    }
+
+    // Scrolling up pauses the auto follow, scrolling back down to the bottom resumes it.
+    fn update_follow_state(&mut self, scroll_area: &scroll_area::ScrollAreaOutput<()>, ui: &Ui) {
+        self.scroll.max_offset =
+            (scroll_area.content_size.y - scroll_area.inner_rect.height()).max(0.);
+        let is_at_bottom = scroll_area.state.offset.y >= self.scroll.max_offset - 1.;
+
+        let is_hovered = ui.rect_contains_pointer(scroll_area.inner_rect);
+        let scroll_delta_y = if is_hovered { ui.input(|i| i.scroll_delta.y) } else { 0. };
+        let is_dragging_scroll_bar = self.log_selection.button_currently_down
+            && self
+                .log_selection
+                .cursor_pos_when_button_was_pressed
+                .is_some_and(|pos| {
+                    pos.x > scroll_area.inner_rect.max.x - ui.spacing().scroll_bar_width
+                        && scroll_area.inner_rect.y_range().contains(pos.y)
+                });
+        let user_scrolled = scroll_delta_y != 0. || is_dragging_scroll_bar;
+
+        if self.scroll.following && !is_at_bottom && (user_scrolled || self.scroll.pending_scroll.is_some()) {
+            self.scroll.pause();
+        } else if !self.scroll.following && is_at_bottom && user_scrolled && scroll_delta_y <= 0. {
+            self.scroll.follow();
+        }
+    }
+
+    fn render_jump_to_bottom_button(&mut self, pane_index: usize, scroll_area_rect: Rect, ui: &mut Ui) {
+        let text = RichText::new(format!(
+            "↓ {} new lines",
+            format_with_thousands_separator(self.scroll.unread_log_count)
+        ));
+        let text_size = measure_widget_text(ui, text.clone());
+        let button_size = text_size + ui.spacing().button_padding * 2. + Vec2::new(16., 8.);
+
+        let pos = Pos2::new(
+            scroll_area_rect.center().x - button_size.x / 2.,
+            scroll_area_rect.bottom() - button_size.y - 12.,
+        );
+
+        Area::new(Id::new(("console_jump_to_bottom", pane_index)))
+            .order(Order::Foreground)
+            .fixed_pos(pos)
+            .show(ui.ctx(), |ui| {
+                if button(text, ui, button_size).clicked() {
+                    self.scroll.follow();
+                }
+            });
     }
 
     fn auto_scroll_to_selection(
@@ -204,84 +357,37 @@ impl ConsoleTab {
         }
     }
 
-    fn kill_gui_and_target(&mut self, data: &AppLaunchConfig) {
-        process::kill(data.target_process_id(), || {
-            tracing::info!("Exiting because Command + F5 was pressed.");
-            self.should_exit_app.store(true, Ordering::Relaxed);
-        });
-    }
-
-    fn render_logs(&mut self, gui_config: &Config, ui: &mut eframe::egui::Ui) {
+    fn render_logs(
+        &mut self,
+        gui_config: &Config,
+        pane_config: &ConsolePaneConfig,
+        store: &LogStore,
+        ui: &mut eframe::egui::Ui,
+    ) {
         let clip_rect = ui.painter().clip_rect();
 
-        let log_count = self.logs.len();
-        for i in 0..log_count {
-            Self::render_log(
-                &mut self.log_heights,
-                &mut self.filter,
-                gui_config,
-                i,
-                ui,
-                &clip_rect,
-                &mut self.log_selection,
-                &mut self.logs[i],
-            );
-
-            self.logs[i].is_selected = is_between(
-                i,
-                self.log_selection.index_of_first_selected_log,
-                self.log_selection.index_of_last_selected_log,
-            );
-        }
-    }
-
-    fn update_copy_logs_to_clipboard(&mut self, ctx: &Context) {
-        if ctx.input(|i| i.modifiers.command) && ctx.input(|i| i.key_pressed(Key::C)) {
-            if let Ok(ctx_) = ClipboardProvider::new() {
-                let mut ctx: ClipboardContext = ctx_;
-
-                let selected_logs: Vec<String> = self
-                    .logs
-                    .iter()
-                    .filter(|x| x.is_selected)
-                    .map(|x| x.data().to_string())
-                    .collect();
-
-                let selected_logs_string = selected_logs.join("\n");
-
-                match ctx.set_contents(selected_logs_string) {
-                    Ok(_) => {}
-                    Err(err) => {
-                        tracing::error!("Failed copying logs to clipboard: {}", err);
-                    }
-                }
+        for (i, log) in store.logs.iter().enumerate() {
+            if !self.is_log_visible(pane_config, store, i) {
+                continue;
             }
+
+            self.render_log(gui_config, i, ui, &clip_rect, log, store.rule_outcomes[i]);
         }
     }
 
-    #[allow(clippy::too_many_arguments)]
     fn render_log(
-        log_heights: &mut HashMap<usize, f32>,
-        filter: &mut Filter,
+        &mut self,
         gui_config: &Config,
         i: usize,
         ui: &mut Ui,
         clip_rect: &Rect,
-        log_selection: &mut LogSelection,
-        log: &mut BepInExLogEntry,
+        log: &BepInExLogEntry,
+        rule_outcome: RuleOutcome,
     ) {
-        if log.level() > gui_config.log_level_filter {
-            return;
-        }
-
-        if !Self::does_log_match_text_filter(&filter.text_lowercase, log) {
-            return;
-        }
-
         let pos_before_log = ui.next_widget_position();
 
         let log_render_decision = make_log_render_decision(
-            log_heights,
+            &mut self.log_heights,
             i,
             pos_before_log,
             ui.ctx().input(|i| i.screen_rect),
@@ -291,43 +397,294 @@ impl ConsoleTab {
             return;
         }
 
-        let log_color = get_color_from_log_level(log, ui.style().visuals.strong_text_color(), gui_config);
+        let log_level = rule_outcome.level.unwrap_or(log.level());
+        let log_color = rule_outcome
+            .color
+            .unwrap_or_else(|| gui_config.log_colors.level(log_level));
 
-        let ui_log_entry = make_ui_log_entry(ui, log, log_color);
+        let ui_log_entry = make_ui_log_entry(ui, log, self.is_log_selected(i), log_color, rule_outcome.bold);
 
         let pos_after_log = ui.next_widget_position();
 
         if log_render_decision == LogRenderDecision::RenderAndCacheHeight {
             let log_height = pos_after_log.y - pos_before_log.y;
-            log_heights.insert(i, log_height);
+            self.log_heights.insert(i, log_height);
         }
 
-        log_selection.update_selection(&ui_log_entry, clip_rect, ui, i);
+        self.log_selection.update_selection(&ui_log_entry, clip_rect, ui, i);
     }
 
-    fn does_log_match_text_filter(text_filter_lowercase: &String, log: &BepInExLogEntry) -> bool {
-        log.data_lowercase().contains(text_filter_lowercase)
+    fn render_follow_toggle(&mut self, ui: &mut Ui) {
+        let text = if self.scroll.following {
+            "Following ⬇"
+        } else {
+            "Paused ⏸"
+        };
+
+        if ui
+            .selectable_label(self.scroll.following, RichText::new(text).small())
+            .on_hover_text("Keep the newest logs in view")
+            .clicked()
+        {
+            if self.scroll.following {
+                self.scroll.pause();
+            } else {
+                self.scroll.follow();
+            }
+        }
+    }
+}
+
+impl ConsoleTab {
+    pub fn new(
+        mod_receiver: Receiver<BepInExMod>,
+        log_receiver: Receiver<BepInExLogEntry>,
+        command_channel: CommandChannel,
+        should_exit_app: Arc<AtomicBool>,
+    ) -> Self {
+        Self {
+            disclaimer: Disclaimer {
+                first_time_showing_it: true,
+                time_when_disclaimer_showed_up: None,
+            },
+            // created from the config on the first update
+            panes: vec![],
+            active_pane_index: 0,
+            target_process_paused: false,
+            mod_receiver,
+            mods: vec![BepInExMod::new("", "")],
+            log_receiver,
+            store: LogStore::default(),
+            show_error_groups: false,
+            command_channel,
+            command_line: CommandLine::default(),
+            should_exit_app,
+        }
+    }
+
+    fn active_pane(&mut self) -> &mut ConsolePane {
+        &mut self.panes[self.active_pane_index]
+    }
+
+    fn render(&mut self, gui_config: &mut Config, ctx: &Context) {
+        if self.show_error_groups {
+            self.render_error_groups_panel(gui_config, ctx);
+        }
+
+        let console_frame =
+            egui::Frame::central_panel(&ctx.style()).fill(gui_config.log_colors.get(LogColorSlot::Background));
+        CentralPanel::default().frame(console_frame).show(ctx, |ui| {
+            if self.store.logs.is_empty() {
+                render_loading_text(ui);
+            } else if self.panes.len() == 1 {
+                self.render_pane(0, ui, gui_config);
+            } else {
+                self.render_split_panes(ui, gui_config);
+            }
+        });
+    }
+
+    fn render_split_panes(&mut self, ui: &mut Ui, gui_config: &mut Config) {
+        let mut pane_to_close = None;
+
+        ui.columns(self.panes.len(), |columns| {
+            for (pane_index, ui) in columns.iter_mut().enumerate() {
+                self.render_pane_header(pane_index, ui, gui_config, &mut pane_to_close);
+                self.render_pane(pane_index, ui, gui_config);
+            }
+        });
+
+        if let Some(pane_index) = pane_to_close {
+            self.panes.remove(pane_index);
+            gui_config.console_panes.remove(pane_index);
+            if self.active_pane_index >= pane_index && self.active_pane_index > 0 {
+                self.active_pane_index -= 1;
+            }
+        }
+    }
+
+    fn render_pane_header(
+        &mut self,
+        pane_index: usize,
+        ui: &mut Ui,
+        gui_config: &Config,
+        pane_to_close: &mut Option<usize>,
+    ) {
+        let pane_config = &gui_config.console_panes[pane_index];
+        let is_active = pane_index == self.active_pane_index;
+
+        ui.horizontal(|ui| {
+            if ui
+                .selectable_label(is_active, RichText::new(format!("Pane {}", pane_index + 1)).small())
+                .on_hover_text("The top bar filters and the footer apply to the active pane")
+                .clicked()
+            {
+                self.active_pane_index = pane_index;
+            }
+
+            let filter_text = if pane_config.filter_text.is_empty() {
+                "All logs"
+            } else {
+                &pane_config.filter_text
+            };
+            ui.label(
+                RichText::new(format!("{filter_text} · {}", pane_config.log_level_filter))
+                    .small()
+                    .weak(),
+            );
+
+            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                if ui.small_button("✖").on_hover_text("Close pane").clicked() {
+                    *pane_to_close = Some(pane_index);
+                }
+            });
+        });
+    }
+
+    fn render_pane(&mut self, pane_index: usize, ui: &mut Ui, gui_config: &Config) {
+        let pane = &mut self.panes[pane_index];
+        if pane.log_selection.button_just_got_down && ui.rect_contains_pointer(ui.max_rect()) {
+            self.active_pane_index = pane_index;
+        }
+
+        pane.render_console_scroll_area(
+            pane_index,
+            ui,
+            gui_config,
+            &gui_config.console_panes[pane_index],
+            &self.store,
+        );
+    }
+
+    fn render_error_groups_panel(&mut self, gui_config: &Config, ctx: &Context) {
+        SidePanel::right("console_error_groups")
+            .resizable(true)
+            .default_width(320.)
+            .show(ctx, |ui| {
+                ui.add_space(4.);
+                let error_groups = &self.store.error_groups;
+                let pane = &mut self.panes[self.active_pane_index];
+
+                ui.horizontal(|ui| {
+                    ui.heading("Errors");
+                    if pane.filter.error_group_index.is_some()
+                        && ui.button("Show all logs").clicked()
+                    {
+                        pane.filter.error_group_index = None;
+                    }
+                });
+                ui.separator();
+
+                if error_groups.is_empty() {
+                    ui.label("No errors so far.");
+                    return;
+                }
+
+                let session_start = self.store.logs[0].timestamp();
+
+                // most frequent first
+                let mut group_indices: Vec<usize> = (0..error_groups.len()).collect();
+                group_indices.sort_by_key(|&i| std::cmp::Reverse(error_groups.groups()[i].count()));
+
+                ScrollArea::vertical()
+                    .auto_shrink([false; 2])
+                    .show(ui, |ui| {
+                        for group_index in group_indices {
+                            let group = &error_groups.groups()[group_index];
+                            let is_selected = pane.filter.error_group_index == Some(group_index);
+
+                            let title = format!("{}× {}", group.count(), group.title());
+                            let color = gui_config.log_colors.level(group.level());
+                            let group_label = ui
+                                .add(SelectableLabel::new(
+                                    is_selected,
+                                    RichText::new(title).color(color).text_style(TextStyle::Small),
+                                ))
+                                .on_hover_text(group.signature());
+
+                            ui.label(
+                                RichText::new(format!(
+                                    "Suspected mod: {}\nFirst: #{} ({})   Last: #{} ({})",
+                                    group.suspected_mod(),
+                                    group.first_index() + 1,
+                                    format_session_time(session_start, group.first_timestamp()),
+                                    group.last_index() + 1,
+                                    format_session_time(session_start, group.last_timestamp()),
+                                ))
+                                .small()
+                                .weak(),
+                            );
+                            ui.separator();
+
+                            if group_label.clicked() {
+                                pane.filter.error_group_index =
+                                    if is_selected { None } else { Some(group_index) };
+                                pane.clear_selection();
+                            }
+                        }
+                    });
+            });
+    }
+
+    fn kill_gui_and_target(&mut self, data: &AppLaunchConfig) {
+        process::kill(data.target_process_id(), || {
+            tracing::info!("Exiting because Command + F5 was pressed.");
+            self.should_exit_app.store(true, Ordering::Relaxed);
+        });
+    }
+
+    fn update_copy_logs_to_clipboard(&mut self, gui_config: &Config, ctx: &Context) {
+        if ctx.input(|i| i.modifiers.command) && ctx.input(|i| i.key_pressed(Key::C)) {
+            if let Ok(ctx_) = ClipboardProvider::new() {
+                let mut ctx: ClipboardContext = ctx_;
+
+                let pane = &self.panes[self.active_pane_index];
+                let pane_config = &gui_config.console_panes[self.active_pane_index];
+                let selected_logs: Vec<String> = (0..self.store.logs.len())
+                    .filter(|&i| pane.is_log_selected(i) && pane.is_log_visible(pane_config, &self.store, i))
+                    .map(|i| self.store.logs[i].data().to_string())
+                    .collect();
+
+                let selected_logs_string = selected_logs.join("\n");
+
+                match ctx.set_contents(selected_logs_string) {
+                    Ok(_) => {}
+                    Err(err) => {
+                        tracing::error!("Failed copying logs to clipboard: {}", err);
+                    }
+                }
+            }
+        }
     }
 
     fn render_footer(&mut self, data: &AppLaunchConfig, gui_config: &mut Config, ctx: &Context) {
         TopBottomPanel::bottom("console_footer").show(ctx, |ui| {
             ui.add_space(2.0);
 
+            if !data.is_offline() {
+                self.render_command_line(ui, gui_config);
+            }
+
             ui.horizontal(|ui| {
                 // TODO: calculate instead of hardcoding
                 if ui.available_width() > 250. {
                     ui.label(RichText::new("Log Level: ").small());
                 }
 
-                let log_level_text = gui_config.log_level_filter.to_string();
+                let pane_config = &mut gui_config.console_panes[self.active_pane_index];
+                let log_level_text = pane_config.log_level_filter.to_string();
                 ui.add(
                     Slider::new(
-                        &mut gui_config.log_level_filter,
+                        &mut pane_config.log_level_filter,
                         LogLevel::Fatal..=LogLevel::All,
                     )
                     .show_value(false)
                     .text(log_level_text),
                 );
+
+                ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                    self.active_pane().render_follow_toggle(ui);
+                });
             });
 
             views::BepInExGUI::render_useful_buttons_footer(
@@ -339,6 +696,120 @@ impl ConsoleTab {
         });
     }
 
+    fn render_command_line(&mut self, ui: &mut Ui, gui_config: &mut Config) {
+        let command_line_id = Id::new("console_command_line");
+        let has_focus = ui.memory(|mem| mem.has_focus(command_line_id));
+
+        let completions = self.command_completions();
+        if has_focus {
+            self.update_command_history_navigation(ui, gui_config, command_line_id);
+
+            if ui.input_mut(|i| i.consume_key(Modifiers::NONE, Key::Tab)) {
+                if let Some(command) = completions.first() {
+                    self.complete_command(ui, command, command_line_id);
+                }
+            }
+        }
+
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(">").monospace());
+
+            let command_line_response = ui
+                .add_enabled(
+                    self.command_channel.is_connected(),
+                    TextEdit::singleline(&mut self.command_line.text)
+                        .id(command_line_id)
+                        .font(TextStyle::Monospace)
+                        .desired_width(f32::INFINITY)
+                        // Tab completes instead of moving the focus
+                        .lock_focus(true)
+                        .hint_text("Send a command to the game (Tab: complete, ↑↓: history)"),
+                )
+                .on_disabled_hover_text("Only available while connected to the game");
+
+            if command_line_response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter)) {
+                self.send_command(gui_config);
+                command_line_response.request_focus();
+            }
+        });
+
+        if has_focus && !completions.is_empty() {
+            ui.horizontal_wrapped(|ui| {
+                for command in completions.iter().take(MAX_COMMAND_SUGGESTION_COUNT) {
+                    let suggestion = ui.small_button(RichText::new(&command.name).monospace());
+                    if suggestion.on_hover_text(&command.description).clicked() {
+                        self.complete_command(ui, command, command_line_id);
+                        ui.memory_mut(|mem| mem.request_focus(command_line_id));
+                    }
+                }
+            });
+        }
+
+        ui.add_space(2.0);
+    }
+
+    // Commands advertised by the loader matching the first word being typed
+    fn command_completions(&self) -> Vec<ConsoleCommand> {
+        let text = &self.command_line.text;
+        if text.is_empty() || text.contains(' ') {
+            return vec![];
+        }
+
+        self.command_channel.completions(text)
+    }
+
+    fn complete_command(&mut self, ui: &Ui, command: &ConsoleCommand, command_line_id: Id) {
+        self.command_line.text = format!("{} ", command.name);
+        move_cursor_to_end(ui, command_line_id, &self.command_line.text);
+    }
+
+    fn update_command_history_navigation(&mut self, ui: &Ui, gui_config: &Config, command_line_id: Id) {
+        let history = &gui_config.command_history;
+
+        let history_index = if ui.input_mut(|i| i.consume_key(Modifiers::NONE, Key::ArrowUp)) {
+            match self.command_line.history_index {
+                None => history.len().checked_sub(1),
+                Some(index) => Some(index.saturating_sub(1)),
+            }
+        } else if ui.input_mut(|i| i.consume_key(Modifiers::NONE, Key::ArrowDown)) {
+            match self.command_line.history_index {
+                Some(index) if index + 1 < history.len() => Some(index + 1),
+                // past the most recent one, back to an empty line
+                _ => None,
+            }
+        } else {
+            return;
+        };
+
+        self.command_line.history_index = history_index;
+        self.command_line.text = history_index
+            .map(|index| history[index].clone())
+            .unwrap_or_default();
+        move_cursor_to_end(ui, command_line_id, &self.command_line.text);
+    }
+
+    fn send_command(&mut self, gui_config: &mut Config) {
+        let command = self.command_line.text.trim().to_string();
+        if command.is_empty() {
+            return;
+        }
+
+        if let Err(err) = self.command_channel.send(&command) {
+            tracing::error!("Failed sending command {:?}: {}", command, err);
+            return;
+        }
+
+        let history = &mut gui_config.command_history;
+        if history.last() != Some(&command) {
+            history.push(command);
+        }
+        if history.len() > MAX_COMMAND_HISTORY_LENGTH {
+            history.remove(0);
+        }
+
+        self.command_line = CommandLine::default();
+    }
+
     fn render_console_first_time_disclaimer(&mut self, ctx: &Context, gui_config: &mut Config) {
         CentralPanel::default().show(ctx, |_| {
             Window::new("Console Disclaimer")
@@ -408,40 +879,68 @@ Please use the buttons below and use the "Copy Log File" button, and drag and dr
         }
     }
 
-    fn render_log_text_filter_input(&mut self, ui: &mut Ui, mods_combo_box: &Response) {
+    fn render_log_text_filter_input(&mut self, ui: &mut Ui, gui_config: &mut Config, mods_combo_box: &Response) {
+        let pane_config = &mut gui_config.console_panes[self.active_pane_index];
         if ui
             .add_sized(
                 mods_combo_box.rect.size(),
-                TextEdit::singleline(&mut self.filter.text)
+                TextEdit::singleline(&mut pane_config.filter_text)
                     .text_color(ui.style().visuals.strong_text_color())
                     .hint_text("Filter Text"),
             )
             .changed()
         {
-            self.filter.text_lowercase = self.filter.text.to_lowercase();
+            self.panes[self.active_pane_index].filter.text_lowercase = pane_config.filter_text.to_lowercase();
+        }
+    }
+
+    fn render_error_groups_toggle(&mut self, ui: &mut Ui) {
+        let text = format!("⚠ Errors: {}", self.store.error_groups.len());
+        if ui
+            .selectable_label(self.show_error_groups, RichText::new(text).small())
+            .on_hover_text("Errors and fatals grouped by exception / message")
+            .clicked()
+        {
+            self.show_error_groups = !self.show_error_groups;
         }
     }
 
-    fn render_log_mod_filter(&mut self, ui: &mut Ui) -> Response {
+    fn render_split_pane_button(&mut self, ui: &mut Ui, gui_config: &mut Config) {
+        let can_split = self.panes.len() < MAX_PANE_COUNT;
+        if ui
+            .add_enabled(can_split, Button::new(RichText::new("◫ Split").small()))
+            .on_hover_text("Add a pane with its own filters, next to the others")
+            .clicked()
+        {
+            let pane_config = ConsolePaneConfig::default();
+            self.panes.push(ConsolePane::new(&pane_config));
+            gui_config.console_panes.push(pane_config);
+            self.active_pane_index = self.panes.len() - 1;
+        }
+    }
+
+    fn render_log_mod_filter(&mut self, ui: &mut Ui, gui_config: &mut Config) -> Response {
+        let filter = &mut self.panes[self.active_pane_index].filter;
         let mods_combo_box = ComboBox::from_id_source("combo_box_mods_log_filter")
             .width(200.)
             .show_index(
                 ui,
-                &mut self.filter.selected_index_in_mods_combo_box,
+                &mut filter.selected_index_in_mods_combo_box,
                 self.mods.len(),
                 |i| self.mods[i].name(),
             );
 
         if mods_combo_box.changed() {
-            self.filter.text = if self.filter.selected_index_in_mods_combo_box == 0 {
+            let pane_config = &mut gui_config.console_panes[self.active_pane_index];
+            pane_config.filter_text = if filter.selected_index_in_mods_combo_box == 0 {
                 String::new()
             } else {
-                self.mods[self.filter.selected_index_in_mods_combo_box]
+                self.mods[filter.selected_index_in_mods_combo_box]
                     .name()
                     .to_string()
             };
 
-            self.filter.text_lowercase = self.filter.text.to_lowercase();
+            filter.text_lowercase = pane_config.filter_text.to_lowercase();
         }
         mods_combo_box
     }
@@ -478,6 +977,27 @@ fn make_log_render_decision(
     LogRenderDecision::RenderNormally
 }
 
+fn render_open_log_file_button(ui: &mut Ui, gui_config: &mut Config) {
+    let open_file_btn_text = RichText::new("Open Log File").heading();
+    let open_file_btn_size = measure_widget_text(ui, open_file_btn_text.clone());
+
+    if button(open_file_btn_text, ui, open_file_btn_size).clicked() {
+        gui_config.log_file_to_open = file_explorer_utils::pick_log_file();
+    }
+}
+
+fn move_cursor_to_end(ui: &Ui, text_edit_id: Id, text: &str) {
+    if let Some(mut state) = TextEditState::load(ui.ctx(), text_edit_id) {
+        let end = CCursor::new(text.chars().count());
+        state.set_ccursor_range(Some(CCursorRange::one(end)));
+        state.store(ui.ctx(), text_edit_id);
+    }
+}
+
+fn does_log_match_text_filter(text_filter_lowercase: &String, log: &BepInExLogEntry) -> bool {
+    log.data_lowercase().contains(text_filter_lowercase)
+}
+
 fn render_loading_text(ui: &mut Ui) {
     ui.centered_and_justified(|ui| {
         let loading_text = "Loading ⌛";
@@ -485,26 +1005,66 @@ fn render_loading_text(ui: &mut Ui) {
     });
 }
 
-fn make_ui_log_entry(ui: &mut Ui, log: &mut BepInExLogEntry, log_color: Color32) -> Response {
-    let ui_log_entry = ui.add(SelectableLabel::new(
-        log.is_selected,
-        RichText::new(log.data()).color(log_color).text_style(TextStyle::Small),
-    ));
-    ui_log_entry
+// There is no bold font, so bold entries get a faint background of their color instead
+fn make_ui_log_entry(ui: &mut Ui, log: &BepInExLogEntry, is_selected: bool, log_color: Color32, bold: bool) -> Response {
+    let highlight = bold.then(|| log_color.gamma_multiply(0.2));
+    let text: WidgetText = match log.ansi_spans() {
+        Some(ansi_spans) => make_ansi_layout_job(ui, log.data(), ansi_spans, log_color, highlight).into(),
+        None => {
+            let mut text = RichText::new(log.data()).color(log_color).text_style(TextStyle::Small);
+            if let Some(highlight) = highlight {
+                text = text.background_color(highlight);
+            }
+            text.into()
+        }
+    };
+
+    ui.add(SelectableLabel::new(is_selected, text))
 }
 
-const ORANGE: Color32 = Color32::from_rgb(255, 128, 0);
-fn get_color_from_log_level(log: &mut BepInExLogEntry, info_log_color: Color32, gui_config: &Config) -> Color32 {
-    // TODO: put the colors in a style
-    match log.level() {
-        LogLevel::None | LogLevel::Fatal => if gui_config.dark_mode {Color32::RED} else {Color32::DARK_RED},
-        LogLevel::Error => if gui_config.dark_mode {Color32::LIGHT_RED} else {Color32::RED},
-        LogLevel::Warning => if gui_config.dark_mode {Color32::YELLOW} else {ORANGE},
-        LogLevel::Message => info_log_color,
-        LogLevel::Info => if gui_config.dark_mode {Color32::LIGHT_GRAY} else {Color32::DARK_GRAY},
-        LogLevel::Debug => Color32::GRAY,
-        LogLevel::All => info_log_color,
+// Text without an explicit ANSI colour keeps the log level color.
+// There is no bold font, so bold text is drawn with the strong text color instead.
+// The highlight of a bold rule goes behind the text without an explicit ANSI background.
+fn make_ansi_layout_job(ui: &Ui, text: &str, ansi_spans: &[AnsiSpan], log_color: Color32, highlight: Option<Color32>) -> LayoutJob {
+    let font_id = TextStyle::Small.resolve(ui.style());
+    let strong_color = ui.style().visuals.strong_text_color();
+    let plain_format = TextFormat {
+        font_id: font_id.clone(),
+        color: log_color,
+        background: highlight.unwrap_or_default(),
+        ..Default::default()
+    };
+
+    let mut job = LayoutJob::default();
+    let mut last_end = 0;
+    for span in ansi_spans {
+        if span.range.start > last_end {
+            job.append(&text[last_end..span.range.start], 0.0, plain_format.clone());
+        }
+
+        let style = &span.style;
+        let default_color = if style.bold { strong_color } else { log_color };
+        let color = style.foreground.unwrap_or(default_color);
+        job.append(
+            &text[span.range.clone()],
+            0.0,
+            TextFormat {
+                font_id: font_id.clone(),
+                color,
+                background: style.background.or(highlight).unwrap_or_default(),
+                italics: style.italic,
+                underline: if style.underline { Stroke::new(1.0, color) } else { Stroke::NONE },
+                ..Default::default()
+            },
+        );
+        last_end = span.range.end;
+    }
+
+    if last_end < text.len() {
+        job.append(&text[last_end..], 0.0, plain_format);
     }
+
+    job
 }
 
 impl Tab for ConsoleTab {
@@ -515,21 +1075,31 @@ impl Tab for ConsoleTab {
     fn update_top_panel(
         &mut self,
         data: &AppLaunchConfig,
-        _gui_config: &mut Config,
+        gui_config: &mut Config,
         ui: &mut eframe::egui::Ui,
     ) {
+        self.sync_panes(gui_config);
+
         egui::menu::bar(ui, move |ui| {
             // controls
             ui.with_layout(Layout::left_to_right(Align::Center), move |ui| {
                 ui.label(RichText::new("Log Filtering: ").small());
-                let mods_combo_box = self.render_log_mod_filter(ui);
-                self.render_log_text_filter_input(ui, &mods_combo_box);
+                let mods_combo_box = self.render_log_mod_filter(ui, gui_config);
+                self.render_log_text_filter_input(ui, gui_config, &mods_combo_box);
+                self.render_error_groups_toggle(ui);
+                self.render_split_pane_button(ui, gui_config);
                 
                 ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
-    
-                    self.render_pause_game_button(ui, data);
-    
-                    self.render_kill_gui_and_game_button(ui, data);
+                    if data.is_offline() {
+                        render_open_log_file_button(ui, gui_config);
+                    }
+
+                    // no game to control when viewing a log file
+                    ui.add_enabled_ui(!data.is_offline(), |ui| {
+                        self.render_pause_game_button(ui, data);
+
+                        self.render_kill_gui_and_game_button(ui, data);
+                    });
                 });
             });
         });
@@ -542,10 +1112,17 @@ impl Tab for ConsoleTab {
         ctx: &eframe::egui::Context,
         _frame: &mut eframe::Frame,
     ) {
-        self.log_selection.update_pointer_state(ctx);
+        self.sync_panes(gui_config);
+        for pane in &mut self.panes {
+            pane.log_selection.update_pointer_state(ctx);
+        }
 
         self.update_mod_receiver();
+        self.update_console_filter_to_open(gui_config);
+        let new_logs_start = self.store.logs.len();
         self.update_log_receiver();
+        self.update_rule_outcomes(gui_config);
+        self.update_unread_log_counts(gui_config, new_logs_start);
 
         if gui_config.first_time_console_disclaimer {
             self.render_console_first_time_disclaimer(ctx, gui_config);
@@ -554,19 +1131,72 @@ impl Tab for ConsoleTab {
 
             self.render(gui_config, ctx);
 
-            self.update_copy_logs_to_clipboard(ctx);
+            self.update_copy_logs_to_clipboard(gui_config, ctx);
         }
 
-        if ctx.input(|i| i.modifiers.command) && ctx.input(|i| i.key_pressed(Key::F5)) {
+        if !data.is_offline() && ctx.input(|i| i.modifiers.command) && ctx.input(|i| i.key_pressed(Key::F5)) {
             self.kill_gui_and_target(data);
         }
     }
 }
 
 impl ConsoleTab {
+    // Filters the active pane on a mod picked in the General tab
+    fn update_console_filter_to_open(&mut self, gui_config: &mut Config) {
+        let Some(mod_name) = gui_config.console_filter_to_open.take() else {
+            return;
+        };
+
+        let filter = &mut self.panes[self.active_pane_index].filter;
+        filter.selected_index_in_mods_combo_box =
+            self.mods.iter().position(|mod_| mod_.name() == mod_name).unwrap_or(0);
+        filter.error_group_index = None;
+        filter.text_lowercase = mod_name.to_lowercase();
+        gui_config.console_panes[self.active_pane_index].filter_text = mod_name;
+    }
+
     fn update_mod_receiver(&mut self) {
-        if let Ok(mod_) = self.mod_receiver.try_recv() {
-            self.mods.push(mod_);
+        for mod_ in self.mod_receiver.try_iter() {
+            bepinex_mod::upsert(&mut self.mods, mod_);
+        }
+    }
+
+    // The panes are created from the config, then both are kept in sync when adding / closing one
+    fn sync_panes(&mut self, gui_config: &mut Config) {
+        if gui_config.console_panes.is_empty() {
+            gui_config.console_panes.push(ConsolePaneConfig::default());
+        }
+
+        if self.panes.len() != gui_config.console_panes.len() {
+            self.panes = gui_config.console_panes.iter().map(ConsolePane::new).collect();
+            self.active_pane_index = self.active_pane_index.min(self.panes.len() - 1);
+        }
+    }
+
+    fn update_rule_outcomes(&mut self, gui_config: &Config) {
+        let log_rules = &gui_config.log_rules;
+        let store = &mut self.store;
+
+        if store.rules_generation != Some(log_rules.generation()) {
+            store.rules_generation = Some(log_rules.generation());
+            store.rule_outcomes.clear();
+        }
+
+        for log in &store.logs[store.rule_outcomes.len()..] {
+            store.rule_outcomes.push(log_rules.evaluate(log));
+        }
+    }
+
+    // Only counts the new logs that the pane would show
+    fn update_unread_log_counts(&mut self, gui_config: &Config, new_logs_start: usize) {
+        for (pane, pane_config) in self.panes.iter_mut().zip(&gui_config.console_panes) {
+            if pane.scroll.following {
+                continue;
+            }
+
+            pane.scroll.unread_log_count += (new_logs_start..self.store.logs.len())
+                .filter(|&i| pane.is_log_visible(pane_config, &self.store, i))
+                .count();
         }
     }
 
@@ -577,7 +1207,8 @@ impl ConsoleTab {
         loop {
             match self.log_receiver.try_recv() {
                 Ok(log) => {
-                    self.logs.push(log);
+                    self.store.error_groups.add(self.store.logs.len(), &log, &self.mods);
+                    self.store.logs.push(log);
                 }
                 Err(err) => match err {
                     crossbeam_channel::TryRecvError::Disconnected
@@ -0,0 +1,398 @@
+use std::collections::HashSet;
+
+use crossbeam_channel::Receiver;
+use eframe::{
+    egui::{
+        CentralPanel, Context, RichText, ScrollArea, SelectableLabel, Sense, Shape, SidePanel,
+        Stroke, TextEdit, TextStyle, Ui,
+    },
+    emath::{Align2, Pos2, Rect, Vec2},
+    epaint::Color32,
+};
+
+use crate::{
+    config::{launch::AppLaunchConfig, Config},
+    data::{
+        bepinex_mod::{self, BepInExMod},
+        dependency_graph::{DependencyGraph, GraphNode, NodeKind},
+        installed_mods::ModInventory,
+    },
+};
+
+use super::Tab;
+
+const NODE_SIZE: Vec2 = Vec2::new(180., 22.);
+const NODE_SPACING: Vec2 = Vec2::new(60., 8.);
+const SIDE_PANEL_WIDTH: f32 = 260.;
+
+pub struct DependenciesTab {
+    mod_receiver: Receiver<BepInExMod>,
+    mods: Vec<BepInExMod>,
+    inventory: ModInventory,
+    graph: DependencyGraph,
+    // rebuilt once the mods stop coming in for the frame
+    is_graph_outdated: bool,
+    selected_node_index: Option<usize>,
+    search_text: String,
+}
+
+impl DependenciesTab {
+    pub fn new(mod_receiver: Receiver<BepInExMod>, data: &AppLaunchConfig) -> Self {
+        Self {
+            mod_receiver,
+            mods: Vec::new(),
+            inventory: scan_inventory(data),
+            graph: DependencyGraph::default(),
+            is_graph_outdated: true,
+            selected_node_index: None,
+            search_text: String::new(),
+        }
+    }
+
+    fn render(&mut self, ctx: &Context) {
+        SidePanel::left("dependencies_side_panel")
+            .resizable(true)
+            .default_width(SIDE_PANEL_WIDTH)
+            .show(ctx, |ui| {
+                ui.add(TextEdit::singleline(&mut self.search_text).hint_text("Search"));
+                ui.add_space(5.);
+
+                ScrollArea::vertical()
+                    .auto_shrink([false; 2])
+                    .show(ui, |ui| {
+                        self.render_node_list(ui);
+
+                        if let Some(selected_node_index) = self.selected_node_index {
+                            ui.separator();
+                            self.render_node_details(selected_node_index, ui);
+                        }
+                    });
+            });
+
+        CentralPanel::default().show(ctx, |ui| {
+            if self.graph.nodes.is_empty() {
+                ui.centered_and_justified(|ui| {
+                    ui.heading("No dependency to show yet ⌛");
+                });
+                return;
+            }
+
+            ScrollArea::both().auto_shrink([false; 2]).show(ui, |ui| {
+                if let Some(clicked_node_index) = self.render_graph(ui) {
+                    self.select(clicked_node_index);
+                }
+            });
+        });
+    }
+
+    // Problems first, so that they can be found without reading the graph
+    fn render_node_list(&mut self, ui: &mut Ui) {
+        let search_text = self.search_text.trim().to_lowercase();
+        let mut node_indices: Vec<usize> = (0..self.graph.nodes.len())
+            .filter(|node_index| {
+                let node = &self.graph.nodes[*node_index];
+                node.name.to_lowercase().contains(&search_text)
+                    || node.id.to_lowercase().contains(&search_text)
+                    || node
+                        .package_id
+                        .as_ref()
+                        .is_some_and(|package_id| package_id.to_lowercase().contains(&search_text))
+            })
+            .collect();
+        node_indices.sort_by_key(|node_index| {
+            let node = &self.graph.nodes[*node_index];
+            (
+                !self.has_problem(*node_index),
+                node.kind == NodeKind::Package,
+                node.name.to_lowercase(),
+            )
+        });
+
+        let mut clicked_node_index = None;
+        for node_index in node_indices {
+            let node = &self.graph.nodes[node_index];
+            let text = RichText::new(format!("{} {}", node_icon(node), node.name))
+                .color(self.node_color(ui, node_index));
+            if ui
+                .add(SelectableLabel::new(
+                    self.selected_node_index == Some(node_index),
+                    text,
+                ))
+                .on_hover_text(&node.id)
+                .clicked()
+            {
+                clicked_node_index = Some(node_index);
+            }
+        }
+
+        if let Some(clicked_node_index) = clicked_node_index {
+            self.select(clicked_node_index);
+        }
+    }
+
+    fn render_node_details(&self, node_index: usize, ui: &mut Ui) {
+        let node = &self.graph.nodes[node_index];
+        ui.heading(&node.name);
+        ui.label(RichText::new(&node.id).weak());
+        if let Some(package_id) = &node.package_id {
+            ui.label(RichText::new(format!("From the package {package_id}")).weak());
+        }
+
+        if node.is_missing {
+            ui.label(
+                RichText::new("Not installed, or didn't load").color(ui.visuals().error_fg_color),
+            );
+        }
+        for missing_node in self.graph.missing_dependencies(node_index) {
+            ui.label(
+                RichText::new(format!("Missing dependency: {}", missing_node.id))
+                    .color(ui.visuals().error_fg_color),
+            );
+        }
+        if self.graph.is_in_cycle(node_index) {
+            ui.label(
+                RichText::new("Part of a dependency cycle, BepInEx can't tell which to load first")
+                    .color(ui.visuals().warn_fg_color),
+            );
+        }
+
+        ui.add_space(5.);
+        ui.label(RichText::new("Depends on").strong());
+        let mut has_dependencies = false;
+        for edge in self.graph.dependencies(node_index) {
+            has_dependencies = true;
+            let dependency = &self.graph.nodes[edge.to];
+            match edge.is_soft {
+                true => ui.label(format!("{} (optional)", dependency.name)),
+                false => ui.label(&dependency.name),
+            };
+        }
+        if !has_dependencies {
+            ui.label(RichText::new("Nothing").weak());
+        }
+
+        ui.add_space(5.);
+        ui.label(RichText::new("Needed by").strong());
+        let mut has_dependents = false;
+        for edge in self.graph.dependents(node_index) {
+            has_dependents = true;
+            ui.label(&self.graph.nodes[edge.from].name);
+        }
+        if !has_dependents {
+            ui.label(RichText::new("Nothing").weak());
+        }
+    }
+
+    // One column per depth, dependencies on the left, returns the clicked node
+    fn render_graph(&self, ui: &mut Ui) -> Option<usize> {
+        let depths = self.graph.depths();
+        let mut column_heights = Vec::new();
+        let mut node_offsets = Vec::with_capacity(depths.len());
+        for depth in &depths {
+            if column_heights.len() <= *depth {
+                column_heights.resize(depth + 1, 0);
+            }
+            node_offsets.push(Vec2::new(
+                *depth as f32 * (NODE_SIZE.x + NODE_SPACING.x),
+                column_heights[*depth] as f32 * (NODE_SIZE.y + NODE_SPACING.y),
+            ));
+            column_heights[*depth] += 1;
+        }
+
+        let graph_size = Vec2::new(
+            column_heights.len() as f32 * (NODE_SIZE.x + NODE_SPACING.x),
+            column_heights.iter().max().copied().unwrap_or_default() as f32
+                * (NODE_SIZE.y + NODE_SPACING.y),
+        );
+        let (rect, response) = ui.allocate_exact_size(graph_size, Sense::click());
+        let painter = ui.painter_at(rect);
+        let node_rects: Vec<Rect> = node_offsets
+            .iter()
+            .map(|offset| Rect::from_min_size(rect.min + *offset, NODE_SIZE))
+            .collect();
+
+        let (dependencies, dependents) = match self.selected_node_index {
+            Some(selected_node_index) => (
+                self.graph.all_dependencies(selected_node_index),
+                self.graph.all_dependents(selected_node_index),
+            ),
+            None => (HashSet::new(), HashSet::new()),
+        };
+        let visuals = ui.visuals();
+        let highlight_color = |node_index: usize| {
+            if self.selected_node_index == Some(node_index) {
+                Some(visuals.strong_text_color())
+            } else if dependencies.contains(&node_index) {
+                Some(visuals.hyperlink_color)
+            } else if dependents.contains(&node_index) {
+                Some(visuals.warn_fg_color)
+            } else {
+                None
+            }
+        };
+
+        for edge in &self.graph.edges {
+            let from = node_rects[edge.from].left_center();
+            let to = node_rects[edge.to].right_center();
+            let is_highlighted =
+                highlight_color(edge.from).is_some() && highlight_color(edge.to).is_some();
+            let stroke = match is_highlighted {
+                true => Stroke::new(2., visuals.text_color()),
+                false => Stroke::new(1., visuals.widgets.noninteractive.bg_stroke.color),
+            };
+
+            match edge.is_soft {
+                true => painter.extend(Shape::dashed_line(&[from, to], stroke, 4., 4.)),
+                false => {
+                    painter.line_segment([from, to], stroke);
+                }
+            }
+        }
+
+        let font_id = TextStyle::Small.resolve(ui.style());
+        for (node_index, node) in self.graph.nodes.iter().enumerate() {
+            let node_rect = node_rects[node_index];
+            let stroke_color = highlight_color(node_index)
+                .unwrap_or(visuals.widgets.noninteractive.bg_stroke.color);
+            painter.rect(
+                node_rect,
+                3.,
+                visuals.extreme_bg_color,
+                Stroke::new(1., stroke_color),
+            );
+            painter.text(
+                node_rect.left_center() + Vec2::new(4., 0.),
+                Align2::LEFT_CENTER,
+                format!("{} {}", node_icon(node), node.name),
+                font_id.clone(),
+                self.node_color(ui, node_index),
+            );
+        }
+
+        let clicked_pos = response
+            .interact_pointer_pos()
+            .filter(|_| response.clicked());
+        let hovered_node_index = response.hover_pos().and_then(|pos| {
+            node_rects
+                .iter()
+                .position(|node_rect| node_rect.contains(pos))
+        });
+        if let Some(hovered_node_index) = hovered_node_index {
+            response.on_hover_text(&self.graph.nodes[hovered_node_index].id);
+        }
+
+        clicked_pos.and_then(|pos: Pos2| {
+            node_rects
+                .iter()
+                .position(|node_rect| node_rect.contains(pos))
+        })
+    }
+
+    fn select(&mut self, node_index: usize) {
+        self.selected_node_index = match self.selected_node_index == Some(node_index) {
+            true => None,
+            false => Some(node_index),
+        };
+    }
+
+    fn has_problem(&self, node_index: usize) -> bool {
+        self.graph.nodes[node_index].is_missing
+            || self.graph.missing_dependencies(node_index).next().is_some()
+            || self.graph.is_in_cycle(node_index)
+    }
+
+    fn node_color(&self, ui: &Ui, node_index: usize) -> Color32 {
+        if self.graph.nodes[node_index].is_missing
+            || self.graph.missing_dependencies(node_index).next().is_some()
+        {
+            ui.visuals().error_fg_color
+        } else if self.graph.is_in_cycle(node_index) {
+            ui.visuals().warn_fg_color
+        } else {
+            ui.visuals().text_color()
+        }
+    }
+
+    fn update_mod_receiver(&mut self) {
+        for mod_ in self.mod_receiver.try_iter() {
+            bepinex_mod::upsert(&mut self.mods, mod_);
+            self.is_graph_outdated = true;
+        }
+
+        if self.is_graph_outdated {
+            self.graph = DependencyGraph::build(&self.mods, &self.inventory);
+            self.is_graph_outdated = false;
+            // indices change with the graph
+            self.selected_node_index = None;
+        }
+    }
+}
+
+fn node_icon(node: &GraphNode) -> &'static str {
+    match node.kind {
+        NodeKind::Plugin => "🔌",
+        NodeKind::Package => "📦",
+    }
+}
+
+fn scan_inventory(data: &AppLaunchConfig) -> ModInventory {
+    if data.game_folder_full_path().as_os_str().is_empty() {
+        ModInventory::default()
+    } else {
+        ModInventory::scan(&data.bepinex_root())
+    }
+}
+
+impl Tab for DependenciesTab {
+    fn name(&self) -> &str {
+        "Dependencies"
+    }
+
+    fn update_top_panel(
+        &mut self,
+        data: &AppLaunchConfig,
+        _gui_config: &mut Config,
+        ui: &mut eframe::egui::Ui,
+    ) {
+        ui.horizontal(|ui| {
+            let missing_count = self
+                .graph
+                .nodes
+                .iter()
+                .filter(|node| node.is_missing)
+                .count();
+            let missing_text = format!("Missing dependencies: {missing_count}");
+            match missing_count {
+                0 => ui.label(missing_text),
+                _ => ui.label(RichText::new(missing_text).color(ui.visuals().error_fg_color)),
+            };
+
+            let cycle_text = format!("Cycles: {}", self.graph.cycles.len());
+            match self.graph.cycles.len() {
+                0 => ui.label(cycle_text),
+                _ => ui.label(RichText::new(cycle_text).color(ui.visuals().warn_fg_color)),
+            };
+
+            if ui
+                .button("Refresh")
+                .on_hover_text("Scan BepInEx/plugins again")
+                .clicked()
+            {
+                self.inventory = scan_inventory(data);
+                self.is_graph_outdated = true;
+            }
+        });
+    }
+
+    fn update(
+        &mut self,
+        _data: &AppLaunchConfig,
+        _gui_config: &mut Config,
+        ctx: &Context,
+        _frame: &mut eframe::Frame,
+    ) {
+        self.update_mod_receiver();
+
+        self.render(ctx);
+    }
+}
@@ -1,29 +1,160 @@
+use std::{collections::HashMap, path::{Path, PathBuf}, time::Duration};
+
+use clipboard::{ClipboardContext, ClipboardProvider};
 use crossbeam_channel::Receiver;
 
 use eframe::{
-    egui::{self, CentralPanel, Context, Layout, ScrollArea, TopBottomPanel, RichText},
+    egui::{self, Button, CentralPanel, CollapsingHeader, Color32, ColorImage, Context, Grid, Label, Layout, ScrollArea, TextEdit, TextureHandle, TextureOptions, TopBottomPanel, RichText, Vec2},
     emath::Align,
 };
 
 use crate::{
     app,
+    backend::{
+        file_explorer_utils,
+        session_recorder::RecordedSession,
+        thunderstore::package_index::{self, PackageInfo},
+    },
     config::{launch::AppLaunchConfig, Config},
-    data::bepinex_mod::BepInExMod,
-    views::utils::egui::measure_widget_text,
+    data::{
+        bepinex_log::{startup_profile::StartupProfile, BepInExLogEntry, LogLevel, CHAINLOADER_DONE_MESSAGE},
+        bepinex_mod::{self, BepInExMod, LoadStatus},
+        installed_mods::{InstalledPackage, ModInventory},
+        mod_actions::{self, ActionLog},
+        mod_list_export::{self, ExportFormat},
+        regression_report::RegressionReport,
+    },
+    views::utils::{egui::measure_widget_text, format_date_time},
 };
 
+use strum::{Display, EnumCount, EnumIter, IntoEnumIterator};
+
 use super::Tab;
 
 pub struct GeneralTab {
     mod_receiver: Receiver<BepInExMod>,
     mods: Vec<BepInExMod>,
+    log_receiver: Receiver<BepInExLogEntry>,
+    // compared with the previous session of the game, `None` when viewing a log file
+    session: Option<RecordedSession>,
+    regression_report: Option<RegressionReport>,
+    // revision of the session the report was made from
+    regression_report_revision: Option<usize>,
+    is_regression_report_outdated: bool,
+    // mods can only be told removed once they all got loaded
+    is_loading_complete: bool,
+    // what is in `BepInEx/plugins`, including what never showed up in the log
+    inventory: ModInventory,
+    // by `Name Version`, updated as the mods come since matching goes through every file name
+    mod_package_indices: HashMap<String, usize>,
+    not_loaded_package_indices: Vec<usize>,
+    orphaned_dlls: Vec<PathBuf>,
+    // renames done from the GUI, to disable mods for the next launch
+    action_log: ActionLog,
+    mod_action_error: Option<String>,
+    // clicked this frame, done once the mods are rendered
+    toggled_package_index: Option<usize>,
+    should_undo_last_mod_action: bool,
+    search_text: String,
+    sort_column: ModColumn,
+    is_sort_descending: bool,
+    // by normalized log source
+    mod_log_counts: HashMap<String, ModLogCounts>,
+    startup_profile: StartupProfile,
+    // `None` when the icon couldn't be read
+    package_icons: HashMap<PathBuf, Option<TextureHandle>>,
+    // by Thunderstore package id, filled in the background once per launch
+    // and again when the URL or the cache duration change
+    package_updates: HashMap<String, PackageInfo>,
+    package_lookup_receiver: Option<Receiver<(String, PackageInfo)>>,
+    // URL and cache duration of the running lookup
+    package_lookup_settings: Option<(String, u64)>,
+}
+
+const PACKAGE_ICON_SIZE: f32 = 16.;
+// explanations of the failures wrap past that
+const MOD_NAME_COLUMN_WIDTH: f32 = 400.;
+
+#[derive(Clone, Copy, PartialEq, Eq, Display, EnumCount, EnumIter)]
+enum ModColumn {
+    Name,
+    Version,
+    #[strum(serialize = "GUID")]
+    Guid,
+    Status,
+    #[strum(serialize = "Load time")]
+    LoadTime,
+    #[strum(serialize = "Warnings / Errors")]
+    LogCounts,
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+struct ModLogCounts {
+    // compared errors first when sorting
+    errors: usize,
+    warnings: usize,
+}
+
+// Failed mods first, then the skipped ones, the others in load order
+fn status_sort_key(mod_: &BepInExMod) -> (u8, Option<usize>) {
+    let status_rank = match mod_.status() {
+        LoadStatus::Failed(_) => 0,
+        LoadStatus::Skipped(_) => 1,
+        LoadStatus::Loading | LoadStatus::Loaded => 2,
+    };
+
+    (status_rank, mod_.load_order_index())
+}
+
+// Log sources are usually the plugin names, give or take the spaces
+fn normalize_log_source(source: &str) -> String {
+    source.to_lowercase().replace([' ', '_'], "")
+}
+
+fn log_counts(mod_log_counts: &HashMap<String, ModLogCounts>, mod_: &BepInExMod) -> ModLogCounts {
+    mod_log_counts
+        .get(&normalize_log_source(mod_.name()))
+        .copied()
+        .unwrap_or_default()
 }
 
 impl GeneralTab {
-    pub fn new(mods_receiver: Receiver<BepInExMod>) -> Self {
+    pub fn new(
+        mods_receiver: Receiver<BepInExMod>,
+        log_receiver: Receiver<BepInExLogEntry>,
+        session: Option<RecordedSession>,
+        data: &AppLaunchConfig,
+    ) -> Self {
         Self {
             mod_receiver: mods_receiver,
             mods: Vec::new(),
+            log_receiver,
+            session,
+            regression_report: None,
+            regression_report_revision: None,
+            is_regression_report_outdated: true,
+            is_loading_complete: false,
+            inventory: if data.game_folder_full_path().as_os_str().is_empty() {
+                ModInventory::default()
+            } else {
+                ModInventory::scan(&data.bepinex_root())
+            },
+            mod_package_indices: HashMap::new(),
+            not_loaded_package_indices: Vec::new(),
+            orphaned_dlls: Vec::new(),
+            action_log: ActionLog::read(),
+            mod_action_error: None,
+            toggled_package_index: None,
+            should_undo_last_mod_action: false,
+            search_text: String::new(),
+            sort_column: ModColumn::Status,
+            is_sort_descending: false,
+            mod_log_counts: HashMap::new(),
+            startup_profile: StartupProfile::default(),
+            package_icons: HashMap::new(),
+            package_updates: HashMap::new(),
+            package_lookup_receiver: None,
+            package_lookup_settings: None,
         }
     }
 
@@ -40,7 +171,7 @@ impl GeneralTab {
         });
     }
 
-    fn render(&mut self, gui_config: &Config, ctx: &Context) {
+    fn render(&mut self, gui_config: &mut Config, ctx: &Context) {
         CentralPanel::default().show(ctx, |ui| {
             if self.mods.is_empty() {
                 ui.vertical_centered_justified(|ui| {
@@ -55,21 +186,631 @@ impl GeneralTab {
                 ScrollArea::vertical()
                     .auto_shrink([false; 2])
                     .show(ui, |ui| {
+                        self.render_regression_report(ui);
+
                         self.render_mods(gui_config, ui);
+
+                        if self.is_loading_complete {
+                            self.render_not_loaded_mods(ui);
+                        }
+
+                        self.render_mod_actions(ui);
                     });
             }
         });
+
+        self.update_mod_actions();
+    }
+
+    fn render_mods(&mut self, gui_config: &mut Config, ui: &mut egui::Ui) {
+        let mut export_format = None;
+        ui.horizontal(|ui| {
+            ui.add(TextEdit::singleline(&mut self.search_text).hint_text("Search by name, version or GUID"));
+
+            ui.menu_button("📋 Copy mod list", |ui| {
+                for format in ExportFormat::iter() {
+                    if ui.button(format.to_string()).clicked() {
+                        export_format = Some(format);
+                        ui.close_menu();
+                    }
+                }
+            })
+            .response
+            .on_hover_text("To share it when asking for help");
+        });
+        ui.add_space(5.);
+
+        if let Some(format) = export_format {
+            self.copy_mod_list(format);
+        }
+
+        let load_times: HashMap<String, Duration> = self
+            .startup_profile
+            .steps()
+            .iter()
+            .filter(|_| self.startup_profile.is_timed())
+            .filter_map(|step| Some((step.mod_().to_string(), step.duration()?)))
+            .collect();
+
+        let search_text = self.search_text.trim().to_lowercase();
+        let mut mods: Vec<&BepInExMod> = self
+            .mods
+            .iter()
+            .filter(|mod_| {
+                [mod_.name(), mod_.version(), mod_.guid().unwrap_or_default()]
+                    .iter()
+                    .any(|field| field.to_lowercase().contains(&search_text))
+            })
+            .collect();
+        mods.sort_by(|a, b| {
+            let ordering = match self.sort_column {
+                ModColumn::Name => a.name().to_lowercase().cmp(&b.name().to_lowercase()),
+                ModColumn::Version => a.version().cmp(b.version()),
+                ModColumn::Guid => a.guid().cmp(&b.guid()),
+                ModColumn::Status => status_sort_key(a).cmp(&status_sort_key(b)),
+                ModColumn::LoadTime => load_times.get(&a.to_string()).cmp(&load_times.get(&b.to_string())),
+                ModColumn::LogCounts => log_counts(&self.mod_log_counts, a).cmp(&log_counts(&self.mod_log_counts, b)),
+            };
+
+            match self.is_sort_descending {
+                true => ordering.reverse(),
+                false => ordering,
+            }
+        });
+
+        let mut clicked_column = None;
+
+        Grid::new("mods_grid")
+            .num_columns(ModColumn::COUNT + 1)
+            .striped(true)
+            .spacing([20., 6.])
+            .show(ui, |ui| {
+                for column in ModColumn::iter() {
+                    let arrow = match (self.sort_column == column, self.is_sort_descending) {
+                        (true, false) => " ⏶",
+                        (true, true) => " ⏷",
+                        (false, _) => "",
+                    };
+                    if ui
+                        .add(Button::new(RichText::new(format!("{column}{arrow}")).strong()).frame(false))
+                        .clicked()
+                    {
+                        clicked_column = Some(column);
+                    }
+                }
+                ui.label("");
+                ui.end_row();
+
+                for mod_ in mods {
+                    let color = Self::status_color(ui, mod_);
+                    let package = self
+                        .mod_package_indices
+                        .get(&mod_.to_string())
+                        .map(|package_index| &self.inventory.packages[*package_index]);
+
+                    ui.vertical(|ui| {
+                        ui.set_max_width(MOD_NAME_COLUMN_WIDTH);
+
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new(mod_.status().icon()).color(color));
+
+                            match package.and_then(|package| package.icon_path.as_ref()) {
+                                Some(icon_path) => {
+                                    if let Some(icon) = Self::package_icon(&mut self.package_icons, ui.ctx(), icon_path) {
+                                        ui.image((icon.id(), Vec2::splat(PACKAGE_ICON_SIZE)));
+                                    }
+                                }
+                                None => {
+                                    ui.add_space(PACKAGE_ICON_SIZE);
+                                }
+                            }
+
+                            let mut details = mod_.details();
+                            if let Some(manifest) = package.and_then(|package| package.manifest.as_ref()) {
+                                details = format!("{}\n\n{details}", manifest.description);
+                            }
+                            ui.label(RichText::new(mod_.name()).color(color))
+                                .on_hover_text(details);
+                        });
+
+                        if let Some(explanation) = mod_.explanation() {
+                            ui.add(Label::new(RichText::new(explanation).color(color).small()).wrap(true));
+                        }
+                    });
+
+                    ui.vertical(|ui| {
+                        ui.label(mod_.version());
+                        if let Some(package) = package {
+                            Self::render_package_update(ui, package, &self.package_updates);
+                        }
+                    });
+                    match mod_.guid() {
+                        Some(guid) => ui.label(guid),
+                        None => ui.label(RichText::new("-").weak()),
+                    };
+
+                    ui.vertical(|ui| {
+                        ui.label(RichText::new(mod_.status().to_string()).color(color));
+                        if package.is_some_and(InstalledPackage::is_disabled) {
+                            ui.label(RichText::new("Disabled for the next launch").weak().small());
+                        }
+                    });
+
+                    match load_times.get(&mod_.to_string()) {
+                        Some(load_time) => ui.label(format!("{} ms", load_time.as_millis())),
+                        None => ui.label(RichText::new("-").weak()),
+                    };
+
+                    let mod_log_counts = log_counts(&self.mod_log_counts, mod_);
+                    let log_counts_text = format!("{} / {}", mod_log_counts.warnings, mod_log_counts.errors);
+                    if mod_log_counts == ModLogCounts::default() {
+                        ui.label(RichText::new(log_counts_text).weak());
+                    } else if ui
+                        .link(RichText::new(log_counts_text).color(ui.visuals().warn_fg_color))
+                        .on_hover_text("Show its logs in the console")
+                        .clicked()
+                    {
+                        gui_config.console_filter_to_open = Some(mod_.name().to_string());
+                    }
+
+                    ui.horizontal(|ui| {
+                        if let Some(package) = package {
+                            Self::render_package_links(ui, package);
+
+                            if Self::render_toggle_button(ui, package) {
+                                self.toggled_package_index = self.mod_package_indices.get(&mod_.to_string()).copied();
+                            }
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+
+        if let Some(column) = clicked_column {
+            self.is_sort_descending = self.sort_column == column && !self.is_sort_descending;
+            self.sort_column = column;
+        }
+    }
+
+    fn copy_mod_list(&self, format: ExportFormat) {
+        let mod_list = mod_list_export::export(format, &self.mods, &self.inventory);
+
+        if let Ok(ctx_) = ClipboardProvider::new() {
+            let mut ctx: ClipboardContext = ctx_;
+            if let Err(err) = ctx.set_contents(mod_list) {
+                tracing::error!("Failed copying the mod list to clipboard: {}", err);
+            }
+        }
+    }
+
+    fn render_package_links(ui: &mut egui::Ui, package: &InstalledPackage) {
+        if let Some(readme_path) = &package.readme_path {
+            if ui.small_button("README").clicked() {
+                file_explorer_utils::open_path_in_explorer(readme_path);
+            }
+        }
+
+        if let Some(manifest) = &package.manifest {
+            if !manifest.website_url.is_empty() {
+                ui.hyperlink_to(RichText::new("Website").small(), &manifest.website_url);
+            }
+        }
+    }
+
+    // Link to the newer version, and whether Thunderstore deprecated it
+    fn render_package_update(
+        ui: &mut egui::Ui,
+        package: &InstalledPackage,
+        package_updates: &HashMap<String, PackageInfo>,
+    ) {
+        let (Some(manifest), Some(package_info)) = (
+            package.manifest.as_ref(),
+            package
+                .thunderstore_id()
+                .and_then(|package_id| package_updates.get(&package_id)),
+        ) else {
+            return;
+        };
+
+        if package_info.is_newer_than(&manifest.version_number) {
+            ui.hyperlink_to(
+                RichText::new(format!("⬆ {}", package_info.latest_version)).small(),
+                &package_info.package_url,
+            )
+            .on_hover_text(format!(
+                "Update available, {} is installed",
+                manifest.version_number
+            ));
+        }
+
+        if package_info.is_deprecated {
+            ui.label(
+                RichText::new("Deprecated")
+                    .small()
+                    .color(ui.visuals().error_fg_color),
+            )
+            .on_hover_text("Marked as deprecated on Thunderstore, look for a replacement");
+        }
+    }
+
+    fn render_toggle_button(ui: &mut egui::Ui, package: &InstalledPackage) -> bool {
+        let (text, hover_text) = if package.is_disabled() {
+            ("Enable", "Load it again on the next launch")
+        } else {
+            ("Disable", "Rename its DLLs to .dll.old so that BepInEx skips it on the next launch")
+        };
+
+        ui.small_button(text).on_hover_text(hover_text).clicked()
+    }
+
+    fn render_mod_actions(&mut self, ui: &mut egui::Ui) {
+        let disabled_package_indices: Vec<usize> = (0..self.inventory.packages.len())
+            .filter(|package_index| self.inventory.packages[*package_index].is_disabled())
+            .collect();
+        let has_mod_actions = self
+            .action_log
+            .actions_in(&self.inventory.plugins_directory)
+            .next()
+            .is_some();
+        if disabled_package_indices.is_empty() && !has_mod_actions && self.mod_action_error.is_none() {
+            return;
+        }
+
+        ui.separator();
+
+        if let Some(mod_action_error) = &self.mod_action_error {
+            ui.label(RichText::new(mod_action_error).color(ui.visuals().error_fg_color));
+        }
+
+        if !disabled_package_indices.is_empty() {
+            CollapsingHeader::new(format!("Disabled mods ({})", disabled_package_indices.len())).show(ui, |ui| {
+                for package_index in disabled_package_indices {
+                    let package = &self.inventory.packages[package_index];
+                    ui.horizontal(|ui| {
+                        ui.label(package.name()).on_hover_text(package.path.to_string_lossy());
+                        if Self::render_toggle_button(ui, package) {
+                            self.toggled_package_index = Some(package_index);
+                        }
+                    });
+                }
+            });
+        }
+
+        if has_mod_actions {
+            CollapsingHeader::new("Changes made to the mods folder").show(ui, |ui| {
+                for action in self.action_log.actions_in(&self.inventory.plugins_directory) {
+                    ui.label(format!(
+                        "{}  {} {}",
+                        format_date_time(action.time),
+                        action.kind,
+                        action.package_name
+                    ));
+                }
+
+                if ui
+                    .button("Undo the last change")
+                    .on_hover_text("Renames the files back, or puts the overwritten configs back")
+                    .clicked()
+                {
+                    self.should_undo_last_mod_action = true;
+                }
+            });
+        }
     }
 
-    fn render_mods(&self, _gui_config: &Config, ui: &mut egui::Ui) {
-        for mod_ in self.mods.as_slice() {
-            ui.label(mod_.to_string());
+    // Done once the mods are rendered, the renames change what is installed
+    fn update_mod_actions(&mut self) {
+        let result = if let Some(package_index) = self.toggled_package_index.take() {
+            let package = &self.inventory.packages[package_index];
+            let action = if package.is_disabled() {
+                mod_actions::enable(package)
+            } else {
+                mod_actions::disable(package)
+            };
+
+            action.map(|action| self.action_log.push(action))
+        } else if std::mem::take(&mut self.should_undo_last_mod_action) {
+            self.action_log.undo_last(&self.inventory.plugins_directory)
+        } else {
+            return;
+        };
+
+        self.mod_action_error = result.err().map(|err| err.to_string());
+        self.rescan_inventory();
+    }
+
+    fn rescan_inventory(&mut self) {
+        self.inventory = ModInventory::scan_plugins_directory(self.inventory.plugins_directory.clone());
+
+        self.mod_package_indices = self
+            .mods
+            .iter()
+            .filter_map(|mod_| {
+                self.inventory
+                    .find_package_index(mod_)
+                    .map(|package_index| (mod_.to_string(), package_index))
+            })
+            .collect();
+
+        if self.is_loading_complete {
+            self.update_not_loaded_mods();
         }
     }
 
+    // Loaded once, shrunk to the size it is shown at
+    fn package_icon(
+        package_icons: &mut HashMap<PathBuf, Option<TextureHandle>>,
+        ctx: &Context,
+        icon_path: &Path,
+    ) -> Option<TextureHandle> {
+        package_icons
+            .entry(icon_path.to_path_buf())
+            .or_insert_with(|| {
+                let image = match image::open(icon_path) {
+                    Ok(image) => image.thumbnail(32, 32).into_rgba8(),
+                    Err(err) => {
+                        tracing::warn!("Failed reading {:?}: {}", icon_path, err);
+                        return None;
+                    }
+                };
+
+                let size = [image.width() as usize, image.height() as usize];
+                let image = ColorImage::from_rgba_unmultiplied(size, image.as_raw());
+                Some(ctx.load_texture(icon_path.to_string_lossy(), image, TextureOptions::LINEAR))
+            })
+            .clone()
+    }
+
+    fn render_not_loaded_mods(&self, ui: &mut egui::Ui) {
+        let not_loaded_packages: Vec<&InstalledPackage> = self
+            .not_loaded_package_indices
+            .iter()
+            .map(|package_index| &self.inventory.packages[*package_index])
+            .collect();
+        let orphaned_dlls = &self.orphaned_dlls;
+        if not_loaded_packages.is_empty() && orphaned_dlls.is_empty() {
+            return;
+        }
+
+        ui.separator();
+
+        if !not_loaded_packages.is_empty() {
+            CollapsingHeader::new(format!(
+                "Installed but not loaded ({})",
+                not_loaded_packages.len()
+            ))
+            .show(ui, |ui| {
+                ui.label(
+                    RichText::new(
+                        "BepInEx didn't load any plugin from these packages. \
+                         Some are only libraries used by other mods.",
+                    )
+                    .weak(),
+                );
+
+                for package in not_loaded_packages {
+                    ui.horizontal(|ui| {
+                        let version = package
+                            .manifest
+                            .as_ref()
+                            .map(|manifest| manifest.version_number.as_str())
+                            .unwrap_or_default();
+                        ui.label(format!("{} {version}", package.name()))
+                            .on_hover_text(package.path.to_string_lossy());
+                        Self::render_package_links(ui, package);
+                    });
+                }
+            });
+        }
+
+        if !orphaned_dlls.is_empty() {
+            CollapsingHeader::new(format!("Orphaned DLLs ({})", orphaned_dlls.len())).show(ui, |ui| {
+                ui.label(
+                    RichText::new("DLLs outside of any package that no plugin got loaded from.").weak(),
+                );
+
+                for dll in orphaned_dlls {
+                    let relative_path = dll
+                        .strip_prefix(&self.inventory.plugins_directory)
+                        .unwrap_or(dll);
+                    if ui
+                        .link(relative_path.to_string_lossy())
+                        .on_hover_text("Show in the file explorer")
+                        .clicked()
+                    {
+                        file_explorer_utils::highlight_path_in_explorer(dll);
+                    }
+                }
+            });
+        }
+    }
+
+    fn status_color(ui: &egui::Ui, mod_: &BepInExMod) -> Color32 {
+        let visuals = ui.visuals();
+
+        match mod_.status() {
+            LoadStatus::Failed(_) => visuals.error_fg_color,
+            LoadStatus::Skipped(_) => visuals.weak_text_color(),
+            _ if mod_.targeted_bepinex_version().is_some() => visuals.warn_fg_color,
+            LoadStatus::Loading | LoadStatus::Loaded => visuals.text_color(),
+        }
+    }
+
+    fn render_failed_mods_banner(&self, ui: &mut egui::Ui) {
+        let failed_mod_count = self
+            .mods
+            .iter()
+            .filter(|mod_| mod_.status().is_failed())
+            .count();
+        if failed_mod_count == 0 {
+            return;
+        }
+
+        let failed_mods_text = match failed_mod_count {
+            1 => "⚠ 1 mod failed to load".to_string(),
+            _ => format!("⚠ {failed_mod_count} mods failed to load"),
+        };
+        ui.label(
+            RichText::new(failed_mods_text)
+                .color(ui.visuals().error_fg_color)
+                .strong(),
+        )
+        .on_hover_text("They are listed first below, with what went wrong");
+    }
+
+    fn render_regression_report(&mut self, ui: &mut egui::Ui) {
+        let Some(session) = &self.session else {
+            return;
+        };
+        let Some(Some(previous_session)) = session.previous() else {
+            return;
+        };
+
+        let revision = session.revision();
+        if self.is_regression_report_outdated || self.regression_report_revision != Some(revision) {
+            let mut report = RegressionReport::compare(previous_session.info(), &session.current());
+            // every mod looks removed until they are all loaded, compared again once they are
+            if !self.is_loading_complete {
+                report.removed_mods.clear();
+            }
+            self.regression_report = Some(report);
+            self.regression_report_revision = Some(revision);
+            self.is_regression_report_outdated = false;
+        }
+        let Some(report) = &self.regression_report else {
+            return;
+        };
+
+        let previous_launch_text = format_date_time(previous_session.info().start_time);
+        if report.is_empty() {
+            let text = match self.is_loading_complete {
+                true => format!("Nothing changed since the launch of {previous_launch_text}"),
+                false => format!("Nothing changed so far since the launch of {previous_launch_text}"),
+            };
+            ui.label(RichText::new(text).weak());
+            ui.separator();
+            return;
+        }
+
+        CollapsingHeader::new(
+            RichText::new(format!("What changed since the launch of {previous_launch_text}")).strong(),
+        )
+        .default_open(true)
+        .show(ui, |ui| {
+            let visuals = ui.visuals().clone();
+
+            for mod_ in &report.added_mods {
+                ui.label(RichText::new(format!("+ {mod_}")).color(visuals.hyperlink_color));
+            }
+            for mod_ in &report.removed_mods {
+                ui.label(RichText::new(format!("- {mod_}")).color(visuals.warn_fg_color));
+            }
+            for change in &report.version_changes {
+                ui.label(format!(
+                    "~ {} {} → {}",
+                    change.name, change.previous_version, change.current_version
+                ));
+            }
+
+            if !report.new_errors.is_empty() {
+                ui.add_space(5.);
+                ui.label(RichText::new("New errors").strong());
+                for (signature, title) in &report.new_errors {
+                    ui.label(RichText::new(title).color(visuals.error_fg_color))
+                        .on_hover_text(signature);
+                }
+            }
+
+            if !report.config_changes.is_empty() {
+                ui.add_space(5.);
+                ui.label(RichText::new("Config changes").strong());
+                for change in &report.config_changes {
+                    ui.label(format!(
+                        "{}: {} → {}",
+                        change.key,
+                        change.previous_value.as_deref().unwrap_or("(none)"),
+                        change.current_value.as_deref().unwrap_or("(none)")
+                    ));
+                }
+            }
+        });
+        ui.separator();
+    }
+
     fn update_mod_receiver(&mut self) {
-        if let Ok(mod_) = self.mod_receiver.try_recv() {
-            self.mods.push(mod_);
+        let mut has_received_mods = false;
+
+        for mod_ in self.mod_receiver.try_iter() {
+            has_received_mods = true;
+            match self.inventory.find_package_index(&mod_) {
+                Some(package_index) => self.mod_package_indices.insert(mod_.to_string(), package_index),
+                None => self.mod_package_indices.remove(&mod_.to_string()),
+            };
+            bepinex_mod::upsert(&mut self.mods, mod_);
+        }
+
+        if has_received_mods && self.is_loading_complete {
+            self.update_not_loaded_mods();
+        }
+    }
+
+    fn update_package_lookup(&mut self, gui_config: &Config) {
+        let lookup_settings = (
+            gui_config.thunderstore_base_url.clone(),
+            gui_config.thunderstore_cache_ttl_hours,
+        );
+        if gui_config.check_mod_updates && self.package_lookup_settings.as_ref() != Some(&lookup_settings) {
+            let package_ids: Vec<String> = self
+                .inventory
+                .packages
+                .iter()
+                .filter_map(InstalledPackage::thunderstore_id)
+                .collect();
+            if package_ids.is_empty() {
+                return;
+            }
+
+            // the previous lookup stops once its receiver is dropped
+            self.package_updates.clear();
+            self.package_lookup_settings = Some(lookup_settings);
+            self.package_lookup_receiver = Some(package_index::spawn_lookup(
+                &gui_config.thunderstore_base_url,
+                Duration::from_secs(gui_config.thunderstore_cache_ttl_hours * 60 * 60),
+                package_ids,
+            ));
+        }
+
+        if let Some(package_lookup_receiver) = &self.package_lookup_receiver {
+            self.package_updates.extend(package_lookup_receiver.try_iter());
+        }
+    }
+
+    // The plugin info list can come after the chainloader is done
+    fn update_not_loaded_mods(&mut self) {
+        self.not_loaded_package_indices = self.inventory.not_loaded_package_indices(&self.mods);
+        self.orphaned_dlls = self.inventory.orphaned_dlls(&self.mods);
+    }
+
+    fn update_log_receiver(&mut self) {
+        let was_loading_complete = self.is_loading_complete;
+
+        for log in self.log_receiver.try_iter() {
+            if log.message() == CHAINLOADER_DONE_MESSAGE {
+                self.is_loading_complete = true;
+                self.is_regression_report_outdated = true;
+            }
+
+            self.startup_profile.add(&log);
+            let log_counts = self.mod_log_counts.entry(normalize_log_source(log.source())).or_default();
+            match log.level() {
+                LogLevel::Warning => log_counts.warnings += 1,
+                LogLevel::Error | LogLevel::Fatal => log_counts.errors += 1,
+                _ => {}
+            }
+        }
+
+        if self.is_loading_complete && !was_loading_complete {
+            self.update_not_loaded_mods();
         }
     }
 }
@@ -96,9 +837,15 @@ impl Tab for GeneralTab {
             });
         });
 
-        let loaded_mod_count = self.mods.len();
+        let loaded_mod_count = self
+            .mods
+            .iter()
+            .filter(|mod_| matches!(mod_.status(), LoadStatus::Loading | LoadStatus::Loaded))
+            .count();
         let loaded_mods_text = format!("Loaded Mods: {loaded_mod_count}");
         ui.label(loaded_mods_text);
+
+        self.render_failed_mods_banner(ui);
     }
 
     fn update(
@@ -109,6 +856,13 @@ impl Tab for GeneralTab {
         _frame: &mut eframe::Frame,
     ) {
         self.update_mod_receiver();
+        self.update_log_receiver();
+        self.update_package_lookup(gui_config);
+
+        if std::mem::take(&mut gui_config.mods_folder_changed) {
+            self.action_log = ActionLog::read();
+            self.rescan_inventory();
+        }
 
         self.render_footer(data, ctx);
 
@@ -0,0 +1,262 @@
+use std::thread;
+
+use crossbeam_channel::Receiver;
+use eframe::egui::{
+    CentralPanel, CollapsingHeader, Context, Grid, Key, RichText, ScrollArea, TextEdit, TextStyle,
+    Ui,
+};
+
+use crate::{
+    backend::process,
+    config::{launch::AppLaunchConfig, Config},
+    data::session_history::{self, SearchHit, StoredSession},
+    views::utils::{format_date_time, format_with_thousands_separator},
+};
+
+use super::Tab;
+
+pub struct HistoryTab {
+    // read from disk when the tab gets shown, `None` until then
+    sessions: Option<Vec<StoredSession>>,
+    search_text: String,
+    // `None` when not searching
+    search_hits: Option<Vec<SearchHit>>,
+    // the logs of every session get decompressed, so it happens in the background
+    search_hits_receiver: Option<Receiver<Vec<SearchHit>>>,
+}
+
+impl HistoryTab {
+    pub const fn new() -> Self {
+        Self {
+            sessions: None,
+            search_text: String::new(),
+            search_hits: None,
+            search_hits_receiver: None,
+        }
+    }
+
+    fn render(&mut self, data: &AppLaunchConfig, gui_config: &mut Config, ctx: &Context) {
+        CentralPanel::default().show(ctx, |ui| {
+            if self.search_hits_receiver.is_some() {
+                ui.centered_and_justified(|ui| {
+                    ui.heading("Searching ⌛");
+                });
+                return;
+            }
+
+            ScrollArea::vertical()
+                .auto_shrink([false; 2])
+                .show(ui, |ui| match &self.search_hits {
+                    Some(search_hits) => render_search_hits(search_hits, data, gui_config, ui),
+                    None => {
+                        let sessions = self.sessions.get_or_insert_with(session_history::list);
+                        render_sessions(sessions, data, gui_config, ui);
+                    }
+                });
+        });
+    }
+
+    fn search(&mut self) {
+        self.search_hits = None;
+        self.search_hits_receiver = None;
+
+        let query = self.search_text.trim().to_string();
+        if query.is_empty() {
+            return;
+        }
+
+        let (search_hits_sender, search_hits_receiver) = crossbeam_channel::bounded(1);
+        thread::spawn(move || {
+            _ = search_hits_sender.send(session_history::search(&query));
+        });
+        self.search_hits_receiver = Some(search_hits_receiver);
+    }
+
+    fn update_search_hits_receiver(&mut self) {
+        let Some(search_hits_receiver) = &self.search_hits_receiver else {
+            return;
+        };
+
+        if let Ok(search_hits) = search_hits_receiver.try_recv() {
+            self.search_hits = Some(search_hits);
+            self.search_hits_receiver = None;
+        }
+    }
+}
+
+fn render_sessions(
+    sessions: &[StoredSession],
+    data: &AppLaunchConfig,
+    gui_config: &mut Config,
+    ui: &mut Ui,
+) {
+    if sessions.is_empty() {
+        ui.centered_and_justified(|ui| {
+            ui.heading("No session saved yet");
+        });
+        return;
+    }
+
+    Grid::new("sessions_grid")
+        .num_columns(8)
+        .striped(true)
+        .spacing([20., 6.])
+        .show(ui, |ui| {
+            for header in [
+                "Started", "Game", "BepInEx", "Duration", "Mods", "Logs", "Errors", "",
+            ] {
+                ui.label(RichText::new(header).strong());
+            }
+            ui.end_row();
+
+            for session in sessions {
+                let info = session.info();
+                let duration = info
+                    .end_time
+                    .duration_since(info.start_time)
+                    .unwrap_or_default()
+                    .as_secs();
+
+                ui.label(format_date_time(info.start_time));
+                ui.label(&info.target_name);
+                ui.label(&info.bepinex_version);
+                ui.label(format!("{}:{:02}", duration / 60, duration % 60));
+                ui.label(info.mods.len().to_string());
+                ui.label(format_with_thousands_separator(info.log_count));
+                ui.label(format_with_thousands_separator(info.error_count));
+                render_open_session_button(session, data, gui_config, ui);
+                ui.end_row();
+            }
+        });
+
+    ui.add_space(10.);
+
+    for session in sessions {
+        let info = session.info();
+        CollapsingHeader::new(format!(
+            "Mods of {} - {}",
+            info.target_name,
+            format_date_time(info.start_time)
+        ))
+        .id_source(info.id())
+        .show(ui, |ui| {
+            for mod_ in &info.mods {
+                ui.label(mod_.to_string());
+            }
+        });
+    }
+}
+
+fn render_search_hits(
+    search_hits: &[SearchHit],
+    data: &AppLaunchConfig,
+    gui_config: &mut Config,
+    ui: &mut Ui,
+) {
+    if search_hits.is_empty() {
+        ui.centered_and_justified(|ui| {
+            ui.heading("No session has matching logs");
+        });
+        return;
+    }
+
+    for search_hit in search_hits {
+        let info = search_hit.session.info();
+
+        ui.horizontal(|ui| {
+            ui.heading(format!(
+                "{} - {}",
+                info.target_name,
+                format_date_time(info.start_time)
+            ));
+            ui.label(format!(
+                "{} matches",
+                format_with_thousands_separator(search_hit.match_count)
+            ));
+            render_open_session_button(&search_hit.session, data, gui_config, ui);
+        });
+
+        for line in &search_hit.lines {
+            ui.label(RichText::new(line).text_style(TextStyle::Monospace));
+        }
+        if search_hit.match_count > search_hit.lines.len() {
+            ui.label(RichText::new("...").weak());
+        }
+
+        ui.separator();
+    }
+}
+
+fn render_open_session_button(
+    session: &StoredSession,
+    data: &AppLaunchConfig,
+    gui_config: &mut Config,
+    ui: &mut Ui,
+) {
+    if !ui
+        .button("Open")
+        .on_hover_text("View the logs of this session")
+        .clicked()
+    {
+        return;
+    }
+
+    match session.extract_logs() {
+        // past sessions are only viewed, never attached to a game
+        Ok(log_file_full_path) if data.is_offline() => {
+            gui_config.log_file_to_open = Some(log_file_full_path);
+        }
+        Ok(log_file_full_path) => process::spawn_log_viewer(&log_file_full_path),
+        Err(err) => tracing::error!("Failed extracting the session logs: {}", err),
+    }
+}
+
+impl Tab for HistoryTab {
+    fn name(&self) -> &str {
+        "History"
+    }
+
+    fn update_top_panel(
+        &mut self,
+        _data: &AppLaunchConfig,
+        _gui_config: &mut Config,
+        ui: &mut eframe::egui::Ui,
+    ) {
+        ui.horizontal(|ui| {
+            let search_response = ui.add(
+                TextEdit::singleline(&mut self.search_text)
+                    .hint_text("Search the logs of every session"),
+            );
+            let is_enter_pressed =
+                search_response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+            if ui.button("Search").clicked() || is_enter_pressed {
+                self.search();
+            }
+
+            if (self.search_hits.is_some() || self.search_hits_receiver.is_some())
+                && ui.button("Clear").clicked()
+            {
+                self.search_text.clear();
+                self.search_hits = None;
+                self.search_hits_receiver = None;
+            }
+
+            if ui.button("Refresh").clicked() {
+                self.sessions = None;
+                self.search();
+            }
+        });
+    }
+
+    fn update(
+        &mut self,
+        data: &AppLaunchConfig,
+        gui_config: &mut Config,
+        ctx: &eframe::egui::Context,
+        _frame: &mut eframe::Frame,
+    ) {
+        self.update_search_hits_receiver();
+
+        self.render(data, gui_config, ctx);
+    }
+}
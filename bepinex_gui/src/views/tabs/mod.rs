@@ -1,8 +1,13 @@
 use crate::config::{launch::AppLaunchConfig, Config};
 
+pub mod configs;
 pub mod console;
+pub mod dependencies;
 pub mod general;
+pub mod history;
+pub mod profiles;
 pub mod settings;
+pub mod stats;
 
 pub trait Tab {
     fn name(&self) -> &str;
@@ -0,0 +1,283 @@
+use eframe::egui::{Button, CentralPanel, Context, Grid, Key, RichText, ScrollArea, TextEdit, Ui};
+
+use crate::{
+    backend::process,
+    config::{launch::AppLaunchConfig, Config},
+    data::mod_profiles::{self, ModProfile, ProfileDiff},
+    views::utils::format_date_time,
+};
+
+use super::Tab;
+
+pub struct ProfilesTab {
+    // read from disk when the tab gets shown, `None` until then
+    profiles: Option<Vec<ModProfile>>,
+    new_profile_name: String,
+    // the profile being previewed, with what switching to it would change
+    preview: Option<(ModProfile, ProfileDiff)>,
+    // (text, is_error) of the last save / switch
+    status: Option<(String, bool)>,
+}
+
+impl ProfilesTab {
+    pub const fn new() -> Self {
+        Self {
+            profiles: None,
+            new_profile_name: String::new(),
+            preview: None,
+            status: None,
+        }
+    }
+
+    fn render(&mut self, data: &AppLaunchConfig, gui_config: &mut Config, ctx: &Context) {
+        CentralPanel::default().show(ctx, |ui| {
+            if data.game_folder_full_path().as_os_str().is_empty() {
+                ui.centered_and_justified(|ui| {
+                    ui.heading("No game folder to make profiles of");
+                });
+                return;
+            }
+
+            if let Some((text, is_error)) = &self.status {
+                let color = match is_error {
+                    true => ui.visuals().error_fg_color,
+                    false => ui.visuals().hyperlink_color,
+                };
+                ui.label(RichText::new(text).color(color));
+                ui.separator();
+            }
+
+            ScrollArea::vertical()
+                .auto_shrink([false; 2])
+                .show(ui, |ui| {
+                    let profiles = self
+                        .profiles
+                        .get_or_insert_with(|| mod_profiles::list(&data.bepinex_root()));
+                    if let Some(profile_action) = render_profiles(profiles, ui) {
+                        self.do_profile_action(profile_action);
+                    }
+
+                    if let Some((profile, diff)) = &self.preview {
+                        ui.separator();
+                        if render_preview(profile, diff, !data.is_offline(), ui) {
+                            self.switch_to_previewed_profile(data, gui_config);
+                        }
+                    }
+                });
+        });
+    }
+
+    fn save_new_profile(&mut self, data: &AppLaunchConfig) {
+        let name = self.new_profile_name.trim();
+        if name.is_empty() {
+            return;
+        }
+
+        let profile =
+            ModProfile::snapshot(name, data.game_folder_full_path(), &data.bepinex_root());
+        self.status = Some(match mod_profiles::save(&profile) {
+            Ok(_) => (format!("Saved the current setup as {name}"), false),
+            Err(err) => (format!("Failed saving the profile: {err}"), true),
+        });
+
+        self.new_profile_name.clear();
+        self.profiles = None;
+    }
+
+    fn do_profile_action(&mut self, profile_action: ProfileAction) {
+        match profile_action {
+            ProfileAction::Preview(profile) => {
+                let diff = ProfileDiff::compare(&profile);
+                self.preview = Some((profile, diff));
+            }
+            ProfileAction::Delete(profile) => {
+                if let Err(err) = mod_profiles::delete(&profile) {
+                    self.status = Some((format!("Failed deleting the profile: {err}"), true));
+                }
+                self.preview = None;
+                self.profiles = None;
+            }
+        }
+    }
+
+    fn switch_to_previewed_profile(&mut self, data: &AppLaunchConfig, gui_config: &mut Config) {
+        let Some((profile, diff)) = self.preview.take() else {
+            return;
+        };
+
+        if data.is_offline() {
+            self.status = Some((
+                "Switching profiles needs the game attached, not a log file".to_string(),
+                true,
+            ));
+            self.preview = Some((profile, diff));
+            return;
+        }
+
+        // the game holds on to the DLLs and rewrites its configs when closing
+        if process::is_game_running(data) {
+            self.status = Some((
+                "The game is running, close it before switching profiles".to_string(),
+                true,
+            ));
+            self.preview = Some((profile, diff));
+            return;
+        }
+
+        // so that switching back is always possible
+        let backup = ModProfile::snapshot(
+            &format!("Before switching to {}", profile.name),
+            data.game_folder_full_path(),
+            &data.bepinex_root(),
+        );
+        if let Err(err) = mod_profiles::save(&backup) {
+            self.status = Some((format!("Failed saving the current setup: {err}"), true));
+            return;
+        }
+
+        self.status = Some(match mod_profiles::apply(&profile, &diff) {
+            Ok(_) => (format!("Switched to {}", profile.name), false),
+            Err(err) => (format!("Failed switching to {}: {err}", profile.name), true),
+        });
+
+        gui_config.mods_folder_changed = true;
+        self.profiles = None;
+    }
+}
+
+enum ProfileAction {
+    Preview(ModProfile),
+    Delete(ModProfile),
+}
+
+fn render_profiles(profiles: &[ModProfile], ui: &mut Ui) -> Option<ProfileAction> {
+    if profiles.is_empty() {
+        ui.label(RichText::new("No profile saved for this game yet").weak());
+        return None;
+    }
+
+    let mut profile_action = None;
+
+    Grid::new("profiles_grid")
+        .num_columns(5)
+        .striped(true)
+        .spacing([20., 6.])
+        .show(ui, |ui| {
+            for header in ["Name", "Saved", "Enabled mods", "Configs", ""] {
+                ui.label(RichText::new(header).strong());
+            }
+            ui.end_row();
+
+            for profile in profiles {
+                ui.label(&profile.name);
+                ui.label(format_date_time(profile.created_time));
+                ui.label(format!(
+                    "{} / {}",
+                    profile.enabled_packages.len(),
+                    profile.enabled_packages.len() + profile.disabled_packages.len()
+                ));
+                ui.label(profile.config_files.len().to_string());
+                ui.horizontal(|ui| {
+                    if ui
+                        .button("Preview")
+                        .on_hover_text("See what switching to it would change")
+                        .clicked()
+                    {
+                        profile_action = Some(ProfileAction::Preview(profile.clone()));
+                    }
+                    if ui.button("Delete").clicked() {
+                        profile_action = Some(ProfileAction::Delete(profile.clone()));
+                    }
+                });
+                ui.end_row();
+            }
+        });
+
+    profile_action
+}
+
+// Returns true when the switch got confirmed
+fn render_preview(profile: &ModProfile, diff: &ProfileDiff, can_switch: bool, ui: &mut Ui) -> bool {
+    ui.heading(format!("Switching to {}", profile.name));
+
+    if diff.is_empty() {
+        ui.label(
+            RichText::new("Nothing would change, the game folder is already set up like this")
+                .weak(),
+        );
+        return false;
+    }
+
+    let visuals = ui.visuals().clone();
+    for key in &diff.packages_to_enable {
+        ui.label(RichText::new(format!("+ Enable {key}")).color(visuals.hyperlink_color));
+    }
+    for key in &diff.packages_to_disable {
+        ui.label(RichText::new(format!("- Disable {key}")).color(visuals.warn_fg_color));
+    }
+    for key in &diff.new_packages {
+        ui.label(
+            RichText::new(format!(
+                "- Disable {key}, installed after the profile got saved"
+            ))
+            .color(visuals.warn_fg_color),
+        );
+    }
+    for path in &diff.changed_config_files {
+        ui.label(format!("~ Overwrite config {path}"));
+    }
+    for key in &diff.missing_packages {
+        ui.label(RichText::new(format!("? {key} is not installed anymore, left as is")).weak());
+    }
+
+    ui.add_space(5.);
+    ui.add_enabled(can_switch, Button::new("Switch"))
+        .on_hover_text("The current setup gets saved as a profile first")
+        .on_disabled_hover_text(
+            "Only while attached to the game, the game folder of a log file is only a guess",
+        )
+        .clicked()
+}
+
+impl Tab for ProfilesTab {
+    fn name(&self) -> &str {
+        "Profiles"
+    }
+
+    fn update_top_panel(
+        &mut self,
+        data: &AppLaunchConfig,
+        _gui_config: &mut Config,
+        ui: &mut eframe::egui::Ui,
+    ) {
+        ui.horizontal(|ui| {
+            let name_response =
+                ui.add(TextEdit::singleline(&mut self.new_profile_name).hint_text("Profile name"));
+            let is_enter_pressed =
+                name_response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+            if ui
+                .button("Save current setup")
+                .on_hover_text("Which mods are enabled, and the configs of BepInEx/config")
+                .clicked()
+                || is_enter_pressed
+            {
+                self.save_new_profile(data);
+            }
+
+            if ui.button("Refresh").clicked() {
+                self.profiles = None;
+                self.preview = None;
+            }
+        });
+    }
+
+    fn update(
+        &mut self,
+        data: &AppLaunchConfig,
+        gui_config: &mut Config,
+        ctx: &Context,
+        _frame: &mut eframe::Frame,
+    ) {
+        self.render(data, gui_config, ctx);
+    }
+}
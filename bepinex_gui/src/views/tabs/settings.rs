@@ -1,9 +1,25 @@
 use std::sync::atomic::Ordering;
 
-use eframe::{egui::{CentralPanel, Context, ScrollArea, Slider, TextStyle, Ui}, epaint::Vec2};
+use eframe::{
+    egui::{
+        Button, CentralPanel, Checkbox, CollapsingHeader, ComboBox, Context, DragValue, Grid, RichText,
+        ScrollArea, Slider, TextEdit, TextStyle, Ui,
+    },
+    epaint::{Color32, Vec2},
+};
+use strum::IntoEnumIterator;
 
 use crate::{
+    backend::{
+        alerts::{AlertCondition, AlertTrigger},
+        thunderstore::package_index,
+    },
     config::{launch::AppLaunchConfig, Config},
+    data::bepinex_log::{
+        rules::{LogRule, RuleTarget},
+        LogLevel,
+    },
+    theme::LogColorSlot,
     views::{components, utils::egui::measure_widget_text},
 };
 
@@ -70,6 +86,8 @@ impl SettingsTab {
         render_switch_theme_button(gui_config, ui, button_size);
         
         render_font_size_slider(gui_config, ui, button_size);
+
+        render_log_color_pickers(gui_config, ui);
     }
 
     fn render_behaviour_settings(&mut self, ui: &mut Ui, gui_config: &mut Config, button_size: Vec2) {
@@ -78,6 +96,14 @@ impl SettingsTab {
         render_close_window_when_game_loaded_checkbox(ui, button_size, gui_config);
 
         render_close_window_when_game_closes_checkbox(gui_config, ui, button_size);
+
+        render_log_rules_editor(gui_config, ui);
+
+        render_alert_triggers_editor(gui_config, ui);
+
+        render_session_history_limit(gui_config, ui);
+
+        render_thunderstore_settings(gui_config, ui);
     }
 }
 
@@ -101,6 +127,263 @@ fn render_font_size_slider(
     }
 }
 
+fn render_log_color_pickers(gui_config: &mut Config, ui: &mut eframe::egui::Ui) {
+    CollapsingHeader::new("Console colors").show(ui, |ui| {
+        Grid::new("settings_log_colors").num_columns(3).show(ui, |ui| {
+            for slot in LogColorSlot::iter() {
+                ui.label(slot.to_string());
+
+                let mut color = gui_config.log_colors.get(slot);
+                if ui.color_edit_button_srgba(&mut color).changed() {
+                    gui_config.log_color_overrides_mut().insert(slot, color);
+                    gui_config.update_log_colors();
+                }
+
+                let is_overridden = gui_config.log_color_overrides_mut().contains_key(&slot);
+                if ui
+                    .add_enabled(is_overridden, Button::new("Reset"))
+                    .clicked()
+                {
+                    gui_config.log_color_overrides_mut().remove(&slot);
+                    gui_config.update_log_colors();
+                }
+                ui.end_row();
+            }
+        });
+    });
+}
+
+fn render_log_rules_editor(gui_config: &mut Config, ui: &mut eframe::egui::Ui) {
+    CollapsingHeader::new("Console rules").show(ui, |ui| {
+        ui.label(
+            RichText::new("Recolor, emphasize, hide or change the level of the matching logs. Later rules win.")
+                .small(),
+        );
+
+        let mut changed = false;
+        let mut rule_to_remove = None;
+
+        ScrollArea::vertical()
+            .id_source("settings_log_rules")
+            .max_height(300.)
+            .show(ui, |ui| {
+                for rule_index in 0..gui_config.log_rules.rules().len() {
+                    let error = gui_config.log_rules.error(rule_index).map(str::to_string);
+                    let rule = &mut gui_config.log_rules.rules_mut()[rule_index];
+
+                    ui.group(|ui| {
+                        changed |= render_log_rule(ui, rule_index, rule, &mut rule_to_remove);
+
+                        if let Some(error) = error {
+                            ui.colored_label(Color32::RED, error);
+                        }
+                    });
+                }
+            });
+
+        if let Some(rule_index) = rule_to_remove {
+            gui_config.log_rules.rules_mut().remove(rule_index);
+            changed = true;
+        }
+
+        if ui.button("Add rule").clicked() {
+            gui_config.log_rules.rules_mut().push(LogRule::default());
+            changed = true;
+        }
+
+        if changed {
+            gui_config.log_rules.rules_changed();
+        }
+    });
+}
+
+fn render_log_rule(
+    ui: &mut Ui,
+    rule_index: usize,
+    rule: &mut LogRule,
+    rule_to_remove: &mut Option<usize>,
+) -> bool {
+    let mut changed = false;
+
+    ui.horizontal(|ui| {
+        changed |= ui.checkbox(&mut rule.enabled, "").changed();
+
+        ComboBox::from_id_source(("log_rule_target", rule_index))
+            .selected_text(rule.target.to_string())
+            .show_ui(ui, |ui| {
+                for target in RuleTarget::iter() {
+                    changed |= ui
+                        .selectable_value(&mut rule.target, target, target.to_string())
+                        .changed();
+                }
+            });
+
+        changed |= ui
+            .add(
+                TextEdit::singleline(&mut rule.pattern)
+                    .hint_text("Pattern")
+                    .desired_width(160.),
+            )
+            .changed();
+        changed |= ui.add(Checkbox::new(&mut rule.is_regex, "Regex")).changed();
+
+        if ui.button("🗑").on_hover_text("Remove rule").clicked() {
+            *rule_to_remove = Some(rule_index);
+        }
+    });
+
+    ui.horizontal(|ui| {
+        let mut has_color = rule.color.is_some();
+        if ui.checkbox(&mut has_color, "Color").changed() {
+            rule.color = has_color.then_some(Color32::LIGHT_BLUE);
+            changed = true;
+        }
+        if let Some(color) = &mut rule.color {
+            changed |= ui.color_edit_button_srgba(color).changed();
+        }
+
+        changed |= ui.checkbox(&mut rule.bold, "Bold").changed();
+        changed |= ui.checkbox(&mut rule.hide, "Hide").changed();
+
+        let level_text = rule
+            .level
+            .map_or_else(|| "Keep level".to_string(), |level| level.to_string());
+        ComboBox::from_id_source(("log_rule_level", rule_index))
+            .selected_text(level_text)
+            .show_ui(ui, |ui| {
+                changed |= ui.selectable_value(&mut rule.level, None, "Keep level").changed();
+                for level in LogLevel::iter().filter(|l| !matches!(l, LogLevel::None | LogLevel::All)) {
+                    changed |= ui
+                        .selectable_value(&mut rule.level, Some(level), level.to_string())
+                        .changed();
+                }
+            });
+    });
+
+    changed
+}
+
+fn render_alert_triggers_editor(gui_config: &mut Config, ui: &mut eframe::egui::Ui) {
+    CollapsingHeader::new("Alerts").show(ui, |ui| {
+        ui.label(RichText::new("Get notified about new logs, e.g. while the game is in focus.").small());
+
+        let mut trigger_to_remove = None;
+
+        ScrollArea::vertical()
+            .id_source("settings_alert_triggers")
+            .max_height(300.)
+            .show(ui, |ui| {
+                for (trigger_index, trigger) in gui_config.alert_triggers.iter_mut().enumerate() {
+                    ui.group(|ui| {
+                        render_alert_trigger(ui, trigger_index, trigger, &mut trigger_to_remove);
+                    });
+                }
+            });
+
+        if let Some(trigger_index) = trigger_to_remove {
+            gui_config.alert_triggers.remove(trigger_index);
+        }
+
+        if ui.button("Add alert").clicked() {
+            gui_config.alert_triggers.push(AlertTrigger::default());
+        }
+    });
+}
+
+fn render_alert_trigger(
+    ui: &mut Ui,
+    trigger_index: usize,
+    trigger: &mut AlertTrigger,
+    trigger_to_remove: &mut Option<usize>,
+) {
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut trigger.enabled, "");
+
+        ComboBox::from_id_source(("alert_condition", trigger_index))
+            .selected_text(trigger.condition.to_string())
+            .show_ui(ui, |ui| {
+                for condition in AlertCondition::iter() {
+                    ui.selectable_value(&mut trigger.condition, condition, condition.to_string());
+                }
+            });
+
+        match trigger.condition {
+            AlertCondition::LevelThreshold => {
+                ComboBox::from_id_source(("alert_level", trigger_index))
+                    .selected_text(format!("{} or worse", trigger.level))
+                    .show_ui(ui, |ui| {
+                        for level in LogLevel::iter().filter(|l| !matches!(l, LogLevel::None | LogLevel::All)) {
+                            ui.selectable_value(&mut trigger.level, level, level.to_string());
+                        }
+                    });
+            }
+            AlertCondition::Regex => {
+                ui.add(
+                    TextEdit::singleline(&mut trigger.pattern)
+                        .hint_text("Regex")
+                        .desired_width(160.),
+                );
+            }
+            AlertCondition::LoadComplete => {}
+        }
+
+        if ui.button("🗑").on_hover_text("Remove alert").clicked() {
+            *trigger_to_remove = Some(trigger_index);
+        }
+    });
+
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut trigger.only_when_unfocused, "Only when unfocused");
+        ui.label("Cooldown");
+        ui.add(
+            DragValue::new(&mut trigger.cooldown_secs)
+                .clamp_range(0..=3600)
+                .suffix("s"),
+        );
+    });
+
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut trigger.toast, "Toast");
+        ui.checkbox(&mut trigger.title_badge, "Title badge");
+        ui.checkbox(&mut trigger.taskbar_attention, "Flash taskbar");
+        ui.checkbox(&mut trigger.sound, "Sound");
+    });
+}
+
+fn render_session_history_limit(gui_config: &mut Config, ui: &mut eframe::egui::Ui) {
+    ui.horizontal(|ui| {
+        ui.label("Sessions kept in history");
+        ui.add(DragValue::new(&mut gui_config.session_history_limit).clamp_range(1..=500))
+            .on_hover_text("The oldest sessions get deleted past that count");
+    });
+}
+
+fn render_thunderstore_settings(gui_config: &mut Config, ui: &mut eframe::egui::Ui) {
+    ui.checkbox(&mut gui_config.check_mod_updates, "Check for mod updates on Thunderstore")
+        .on_hover_text("Checks as soon as it is enabled");
+
+    ui.add_enabled_ui(gui_config.check_mod_updates, |ui| {
+        ui.horizontal(|ui| {
+            ui.label("Thunderstore URL");
+            ui.add(
+                TextEdit::singleline(&mut gui_config.thunderstore_base_url)
+                    .hint_text(package_index::DEFAULT_BASE_URL)
+                    .desired_width(240.),
+            )
+            .on_hover_text("Can point to a local mirror");
+        });
+        ui.horizontal(|ui| {
+            ui.label("Check again after");
+            ui.add(
+                DragValue::new(&mut gui_config.thunderstore_cache_ttl_hours)
+                    .clamp_range(0..=24 * 7)
+                    .suffix("h"),
+            )
+            .on_hover_text("Results are cached on disk until then");
+        });
+    });
+}
+
 fn render_close_window_when_game_loaded_checkbox(
     ui: &mut eframe::egui::Ui,
     space: eframe::epaint::Vec2,
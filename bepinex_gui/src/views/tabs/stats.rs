@@ -0,0 +1,516 @@
+use std::time::Duration;
+
+use crossbeam_channel::Receiver;
+use eframe::{
+    egui::{
+        CentralPanel, Context, Grid, Layout, RichText, ScrollArea, Sense, Stroke, TextStyle, Ui,
+    },
+    emath::{Align, Align2, Pos2, Rect, Vec2},
+};
+use strum::{Display, EnumIter, IntoEnumIterator};
+
+use crate::{
+    config::{launch::AppLaunchConfig, Config},
+    data::bepinex_log::{
+        startup_profile::StartupProfile, stats::LogStats, BepInExLogEntry, LogLevel,
+    },
+    views::utils::{format_session_time, format_with_thousands_separator},
+};
+
+use super::Tab;
+
+const TOP_TALKER_COUNT: usize = 15;
+const CHART_HEIGHT: f32 = 140.;
+// minimum width of a chart column, older seconds get merged together past that
+const CHART_COLUMN_WIDTH: f32 = 3.;
+const TIMELINE_HEIGHT: f32 = 40.;
+// plugins taking more than this share of the startup get highlighted
+const SLOW_PLUGIN_SHARE: f32 = 0.1;
+
+#[derive(Clone, Copy, PartialEq, Eq, Display, EnumIter)]
+enum StatsView {
+    Logs,
+    #[strum(serialize = "Startup profile")]
+    StartupProfile,
+}
+
+pub struct StatsTab {
+    log_receiver: Receiver<BepInExLogEntry>,
+    stats: LogStats,
+    startup_profile: StartupProfile,
+    view: StatsView,
+}
+
+impl StatsTab {
+    pub fn new(log_receiver: Receiver<BepInExLogEntry>) -> Self {
+        Self {
+            log_receiver,
+            stats: LogStats::default(),
+            startup_profile: StartupProfile::default(),
+            view: StatsView::Logs,
+        }
+    }
+
+    fn render(&mut self, gui_config: &Config, ctx: &Context) {
+        match self.view {
+            StatsView::Logs => self.render_log_stats(gui_config, ctx),
+            StatsView::StartupProfile => self.render_startup_profile(gui_config, ctx),
+        }
+    }
+
+    fn render_startup_profile(&self, gui_config: &Config, ctx: &Context) {
+        CentralPanel::default().show(ctx, |ui| {
+            let profile = &self.startup_profile;
+            let Some(total_duration) = profile.total_duration() else {
+                ui.centered_and_justified(|ui| {
+                    ui.heading("Waiting for the chainloader ⌛");
+                });
+                return;
+            };
+
+            if !profile.is_timed() {
+                ui.heading(format!("{} plugins loaded", profile.steps().len()));
+                ui.label(
+                    RichText::new(
+                        "Timing unavailable, the chainloader lines came from the log file \
+                         and only the loader knows when they got logged.",
+                    )
+                    .weak(),
+                );
+                return;
+            }
+
+            let summary = if profile.completion().is_some() {
+                format!(
+                    "{} plugins loaded in {}",
+                    profile.steps().len(),
+                    format_duration(total_duration)
+                )
+            } else {
+                format!(
+                    "Loading... {} plugins so far, {}",
+                    profile.steps().len(),
+                    format_duration(total_duration)
+                )
+            };
+            ui.heading(summary);
+            ui.label(
+                RichText::new(
+                    "Load times are measured from when the loader logged the chainloader lines.",
+                )
+                .small()
+                .weak(),
+            );
+            ui.add_space(6.);
+
+            self.render_startup_timeline(gui_config, total_duration, ui);
+            ui.add_space(10.);
+
+            ui.spacing_mut().scroll_bar_width = 16.;
+            ScrollArea::vertical()
+                .auto_shrink([false; 2])
+                .show(ui, |ui| {
+                    self.render_plugin_load_ranking(gui_config, total_duration, ui);
+                });
+        });
+    }
+
+    // One segment per plugin, as wide as its load time
+    fn render_startup_timeline(&self, gui_config: &Config, total_duration: Duration, ui: &mut Ui) {
+        let profile = &self.startup_profile;
+        let Some(start) = profile.start() else {
+            return;
+        };
+
+        let (rect, response) = ui.allocate_exact_size(
+            Vec2::new(ui.available_width(), TIMELINE_HEIGHT),
+            Sense::hover(),
+        );
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 2., ui.visuals().extreme_bg_color);
+
+        let total_secs = total_duration.as_secs_f32().max(f32::EPSILON);
+        let x_from_duration =
+            |duration: Duration| rect.left() + duration.as_secs_f32() / total_secs * rect.width();
+
+        let normal_color = gui_config.log_colors.level(LogLevel::Info);
+        let slow_color = gui_config.log_colors.level(LogLevel::Warning);
+        let hover_pos = response.hover_pos();
+        let mut hovered_step = None;
+        for (i, step) in profile.steps().iter().enumerate() {
+            let step_start = step.start().duration_since(start).unwrap_or_default();
+            let step_duration = step
+                .duration()
+                .unwrap_or_else(|| total_duration.saturating_sub(step_start));
+
+            let left = x_from_duration(step_start);
+            let right = x_from_duration(step_start + step_duration).max(left + 1.);
+            let step_rect =
+                Rect::from_min_max(Pos2::new(left, rect.top()), Pos2::new(right, rect.bottom()));
+
+            let is_slow = step_duration.as_secs_f32() / total_secs >= SLOW_PLUGIN_SHARE;
+            let mut color = if is_slow { slow_color } else { normal_color };
+            // alternate the shades so that neighbours can be told apart
+            color = color.gamma_multiply(if i % 2 == 0 { 0.8 } else { 0.5 });
+            if hover_pos.is_some_and(|pos| step_rect.x_range().contains(pos.x)) {
+                color = ui.visuals().strong_text_color();
+                hovered_step = Some(step);
+            }
+            painter.rect_filled(step_rect, 0., color);
+        }
+
+        if let Some(step) = hovered_step {
+            let duration_text = step
+                .duration()
+                .map_or_else(|| "loading...".to_string(), format_duration);
+            response.on_hover_text(format!("{}\n{}", step.mod_(), duration_text));
+        }
+    }
+
+    fn render_plugin_load_ranking(
+        &self,
+        gui_config: &Config,
+        total_duration: Duration,
+        ui: &mut Ui,
+    ) {
+        let profile = &self.startup_profile;
+        let total_secs = total_duration.as_secs_f32().max(f32::EPSILON);
+        let slow_color = gui_config.log_colors.level(LogLevel::Warning);
+
+        Grid::new("stats_plugin_load_ranking")
+            .num_columns(5)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label(RichText::new("#").strong());
+                ui.label(RichText::new("Plugin").strong());
+                ui.label(RichText::new("Version").strong());
+                ui.label(RichText::new("Load time").strong());
+                ui.label(RichText::new("Share").strong());
+                ui.end_row();
+
+                for (rank, step_index) in profile.ranking().into_iter().enumerate() {
+                    let step = &profile.steps()[step_index];
+
+                    ui.label((rank + 1).to_string());
+                    ui.label(step.mod_().name());
+                    ui.label(RichText::new(step.mod_().version()).weak());
+
+                    match step.duration() {
+                        Some(duration) => {
+                            let share = duration.as_secs_f32() / total_secs;
+                            let mut duration_text = RichText::new(format_duration(duration));
+                            if share >= SLOW_PLUGIN_SHARE {
+                                duration_text = duration_text.color(slow_color);
+                            }
+                            ui.label(duration_text);
+                            ui.label(RichText::new(format!("{:.1}%", share * 100.)).weak());
+                        }
+                        None => {
+                            ui.label(RichText::new("loading...").weak());
+                            ui.label("");
+                        }
+                    }
+                    ui.end_row();
+                }
+            });
+    }
+
+    fn render_log_stats(&self, gui_config: &Config, ctx: &Context) {
+        CentralPanel::default().show(ctx, |ui| {
+            if self.stats.total_count() == 0 {
+                ui.centered_and_justified(|ui| {
+                    ui.heading("Loading ⌛");
+                });
+                return;
+            }
+
+            ui.spacing_mut().scroll_bar_width = 16.;
+            ScrollArea::vertical()
+                .auto_shrink([false; 2])
+                .show(ui, |ui| {
+                    ui.heading("Lines per second");
+                    self.render_lines_per_second_timing(gui_config, ui);
+                    ui.add_space(10.);
+
+                    ui.columns(2, |columns| {
+                        columns[0].heading("Levels");
+                        self.render_level_counts(gui_config, &mut columns[0]);
+
+                        columns[1].heading("Top talkers");
+                        self.render_top_talkers(gui_config, &mut columns[1]);
+                    });
+                });
+        });
+    }
+
+    fn render_lines_per_second_timing(&self, gui_config: &Config, ui: &mut Ui) {
+        if self.stats.lines_per_second().is_empty() {
+            ui.label(RichText::new("Timing unavailable, the lines came from the log file").weak());
+            return;
+        }
+
+        self.render_lines_per_second_chart(gui_config, ui);
+        if self.stats.untimed_count() > 0 {
+            ui.label(
+                RichText::new(format!(
+                    "{} lines read back from the log file are left out",
+                    format_with_thousands_separator(self.stats.untimed_count())
+                ))
+                .small()
+                .weak(),
+            );
+        }
+    }
+
+    fn render_level_counts(&self, gui_config: &Config, ui: &mut Ui) {
+        Grid::new("stats_levels")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                for level in
+                    LogLevel::iter().filter(|l| !matches!(l, LogLevel::None | LogLevel::All))
+                {
+                    let count = self.stats.count(level);
+                    ui.label(
+                        RichText::new(level.to_string()).color(gui_config.log_colors.level(level)),
+                    );
+                    ui.label(format_with_thousands_separator(count));
+                    ui.label(RichText::new(format_share(count, self.stats.total_count())).weak());
+                    ui.end_row();
+                }
+
+                ui.label(RichText::new("Total").strong());
+                ui.label(
+                    RichText::new(format_with_thousands_separator(self.stats.total_count()))
+                        .strong(),
+                );
+                ui.end_row();
+            });
+    }
+
+    fn render_top_talkers(&self, gui_config: &Config, ui: &mut Ui) {
+        ui.label(
+            RichText::new(format!("{} sources", self.stats.source_count()))
+                .small()
+                .weak(),
+        );
+
+        Grid::new("stats_top_talkers")
+            .num_columns(4)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label(RichText::new("Source").strong());
+                ui.label(RichText::new("Lines").strong());
+                ui.label(RichText::new("Share").strong());
+                ui.label(RichText::new("Errors").strong());
+                ui.end_row();
+
+                for (source, source_stats) in self.stats.top_talkers(TOP_TALKER_COUNT) {
+                    ui.label(if source.is_empty() {
+                        "(no source)"
+                    } else {
+                        source
+                    });
+                    ui.label(format_with_thousands_separator(source_stats.count()));
+                    ui.label(
+                        RichText::new(format_share(source_stats.count(), self.stats.total_count()))
+                            .weak(),
+                    );
+
+                    let error_count = source_stats.error_count();
+                    let error_color = if error_count > 0 {
+                        gui_config.log_colors.level(LogLevel::Error)
+                    } else {
+                        ui.visuals().weak_text_color()
+                    };
+                    ui.label(
+                        RichText::new(format_with_thousands_separator(error_count))
+                            .color(error_color),
+                    );
+                    ui.end_row();
+                }
+            });
+    }
+
+    // Bars of the lines per second, with the errors drawn on top of them
+    fn render_lines_per_second_chart(&self, gui_config: &Config, ui: &mut Ui) {
+        let lines_per_second = self.stats.lines_per_second();
+        let errors_per_second = self.stats.errors_per_second();
+
+        let (rect, response) = ui.allocate_exact_size(
+            Vec2::new(ui.available_width(), CHART_HEIGHT),
+            Sense::hover(),
+        );
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 2., ui.visuals().extreme_bg_color);
+
+        let max_column_count = ((rect.width() / CHART_COLUMN_WIDTH) as usize).max(1);
+        let seconds_per_column = lines_per_second.len().div_ceil(max_column_count).max(1);
+        let column_count = lines_per_second.len().div_ceil(seconds_per_column);
+        let column_width = rect.width() / column_count as f32;
+
+        // peak of a column, so that bursts stay visible once merged
+        let column_peak = |values: &[u32], column: usize| {
+            let start = column * seconds_per_column;
+            let end = (start + seconds_per_column).min(values.len());
+            values[start..end].iter().copied().max().unwrap_or(0)
+        };
+
+        let peak = lines_per_second.iter().copied().max().unwrap_or(0).max(1);
+        let bar_height = |value: u32| value as f32 / peak as f32 * (rect.height() - 4.);
+
+        let line_color = gui_config.log_colors.level(LogLevel::Info);
+        let error_color = gui_config.log_colors.level(LogLevel::Error);
+        for column in 0..column_count {
+            let left = rect.left() + column as f32 * column_width;
+            let right = left + (column_width - 1.).max(1.);
+
+            let lines = column_peak(lines_per_second, column);
+            let lines_rect = Rect::from_min_max(
+                Pos2::new(left, rect.bottom() - bar_height(lines)),
+                Pos2::new(right, rect.bottom()),
+            );
+            painter.rect_filled(lines_rect, 0., line_color.gamma_multiply(0.6));
+
+            let errors = column_peak(errors_per_second, column);
+            if errors > 0 {
+                let errors_rect = Rect::from_min_max(
+                    Pos2::new(left, rect.bottom() - bar_height(errors)),
+                    Pos2::new(right, rect.bottom()),
+                );
+                painter.rect_filled(errors_rect, 0., error_color);
+            }
+        }
+
+        if let (Some(session_start), Some(first_error)) = (
+            self.stats.session_start(),
+            self.stats.first_error_timestamp(),
+        ) {
+            let second = first_error
+                .duration_since(session_start)
+                .unwrap_or_default()
+                .as_secs() as usize;
+            let x = rect.left() + (second / seconds_per_column) as f32 * column_width;
+            painter.vline(x, rect.y_range(), Stroke::new(1., error_color));
+            painter.text(
+                Pos2::new(x + 3., rect.top() + 2.),
+                Align2::LEFT_TOP,
+                format!(
+                    "first error {}",
+                    format_session_time(session_start, first_error)
+                ),
+                TextStyle::Small.resolve(ui.style()),
+                error_color,
+            );
+        }
+
+        painter.text(
+            rect.right_top() + Vec2::new(-3., 2.),
+            Align2::RIGHT_TOP,
+            format!("peak {peak}/s"),
+            TextStyle::Small.resolve(ui.style()),
+            ui.visuals().weak_text_color(),
+        );
+
+        if let Some(hover_pos) = response.hover_pos() {
+            let column =
+                (((hover_pos.x - rect.left()) / column_width) as usize).min(column_count - 1);
+            let start_second = column * seconds_per_column;
+            response.on_hover_text(format!(
+                "+{:02}:{:02}\n{} lines/s, {} errors/s",
+                start_second / 60,
+                start_second % 60,
+                column_peak(lines_per_second, column),
+                column_peak(errors_per_second, column),
+            ));
+        }
+
+        ui.with_layout(Layout::left_to_right(Align::Center), |ui| {
+            ui.label(RichText::new("+00:00").small().weak());
+            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                let session_length = lines_per_second.len().saturating_sub(1);
+                ui.label(
+                    RichText::new(format!(
+                        "+{:02}:{:02}",
+                        session_length / 60,
+                        session_length % 60
+                    ))
+                    .small()
+                    .weak(),
+                );
+            });
+        });
+    }
+
+    fn update_log_receiver(&mut self) {
+        // same as the console, drain it so that the stats aren't tied to the framerate
+        while let Ok(log) = self.log_receiver.try_recv() {
+            self.stats.add(&log);
+            self.startup_profile.add(&log);
+        }
+    }
+}
+
+// e.g. `850 ms` or `2.35 s`
+fn format_duration(duration: Duration) -> String {
+    if duration < Duration::from_secs(1) {
+        format!("{} ms", duration.as_millis())
+    } else {
+        format!("{:.2} s", duration.as_secs_f32())
+    }
+}
+
+// e.g. `12.5%`
+fn format_share(count: usize, total: usize) -> String {
+    if total == 0 {
+        return String::new();
+    }
+
+    format!("{:.1}%", count as f32 / total as f32 * 100.)
+}
+
+impl Tab for StatsTab {
+    fn name(&self) -> &str {
+        "Stats"
+    }
+
+    fn update_top_panel(
+        &mut self,
+        _data: &AppLaunchConfig,
+        _gui_config: &mut Config,
+        ui: &mut eframe::egui::Ui,
+    ) {
+        ui.horizontal(|ui| {
+            for view in StatsView::iter() {
+                ui.selectable_value(
+                    &mut self.view,
+                    view,
+                    RichText::new(view.to_string()).small(),
+                );
+            }
+
+            ui.separator();
+
+            ui.label(
+                RichText::new(format!(
+                    "{} lines from {} sources",
+                    format_with_thousands_separator(self.stats.total_count()),
+                    self.stats.source_count()
+                ))
+                .small(),
+            );
+        });
+    }
+
+    fn update(
+        &mut self,
+        _data: &AppLaunchConfig,
+        gui_config: &mut Config,
+        ctx: &eframe::egui::Context,
+        _frame: &mut eframe::Frame,
+    ) {
+        self.update_log_receiver();
+
+        self.render(gui_config, ctx);
+    }
+}
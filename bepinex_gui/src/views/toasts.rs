@@ -0,0 +1,40 @@
+use eframe::egui::{Align2, Area, Context, Frame, Order, RichText, Sense, Vec2};
+
+use crate::{backend::alerts::Alerts, config::Config, theme};
+
+pub fn show(alerts: &mut Alerts, config: &Config, ctx: &Context) {
+    if alerts.toasts().is_empty() {
+        return;
+    }
+
+    let mut toast_to_dismiss = None;
+
+    Area::new("alert_toasts")
+        .order(Order::Foreground)
+        .anchor(Align2::RIGHT_BOTTOM, Vec2::new(-theme::view_padding(), -theme::view_padding()))
+        .show(ctx, |ui| {
+            ui.set_max_width(360.);
+
+            for (i, toast) in alerts.toasts().iter().enumerate().rev() {
+                let response = Frame::popup(ui.style())
+                    .show(ui, |ui| {
+                        ui.label(
+                            RichText::new(&toast.text)
+                                .color(config.log_colors.level(toast.level))
+                                .small(),
+                        );
+                    })
+                    .response
+                    .interact(Sense::click())
+                    .on_hover_text("Click to dismiss");
+
+                if response.clicked() {
+                    toast_to_dismiss = Some(i);
+                }
+            }
+        });
+
+    if let Some(i) = toast_to_dismiss {
+        alerts.dismiss_toast(i);
+    }
+}
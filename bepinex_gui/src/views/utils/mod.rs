@@ -1 +1,51 @@
+use std::time::{Duration, SystemTime};
+
 pub mod egui;
+
+// e.g. `+01:23` since the first received log
+pub fn format_session_time(session_start: SystemTime, time: SystemTime) -> String {
+    let elapsed = time
+        .duration_since(session_start)
+        .unwrap_or(Duration::ZERO)
+        .as_secs();
+    format!("+{:02}:{:02}", elapsed / 60, elapsed % 60)
+}
+
+// e.g. `1,243`
+pub fn format_with_thousands_separator(value: usize) -> String {
+    let digits = value.to_string();
+
+    digits
+        .as_bytes()
+        .rchunks(3)
+        .rev()
+        .map(|chunk| std::str::from_utf8(chunk).unwrap())
+        .collect::<Vec<&str>>()
+        .join(",")
+}
+
+// e.g. `2023-05-14 18:03 UTC`, no timezone database to go with local time
+pub fn format_date_time(time: SystemTime) -> String {
+    let seconds = time
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+        .as_secs();
+    let (days, seconds_of_day) = (seconds / 86_400, seconds % 86_400);
+
+    // days to civil date, from Howard Hinnant's `civil_from_days`
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year}-{month:02}-{day:02} {:02}:{:02} UTC",
+        seconds_of_day / 3600,
+        seconds_of_day % 3600 / 60
+    )
+}